@@ -0,0 +1,285 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::blocks::{self, CustomBlock};
+use crate::crypto::{digest, DisplayFormat, HashAlgorithm, HashParams, InputFormat};
+use crate::export::csv_field;
+
+/// 界面中最多保留的行数，超出部分只写入输出文件
+pub const BATCH_ROW_LIMIT: usize = 200_000;
+
+/// 每批发送给界面的最大行数
+const CHUNK_ROWS: usize = 1000;
+
+/// 每行使用的计算方式
+#[derive(Clone)]
+pub enum BatchRecipe {
+    /// 单一算法，只对口令计算 (忽略盐值)
+    Algorithm(HashAlgorithm, HashParams),
+    /// 自定义积木公式，$pass / $salt 取自每一行
+    Formula(Vec<CustomBlock>),
+}
+
+impl BatchRecipe {
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            BatchRecipe::Algorithm(algo, params) => params.validate(*algo),
+            BatchRecipe::Formula(blocks) => blocks::validate(blocks),
+        }
+    }
+
+    /// 显示与导出用的算法说明
+    pub fn describe(&self) -> String {
+        match self {
+            BatchRecipe::Algorithm(algo, params) => {
+                let tag = params.tag(*algo);
+                if tag.is_empty() { algo.name().to_string() } else { format!("{} [{}]", algo.name(), tag) }
+            }
+            BatchRecipe::Formula(blocks) => blocks::formula(blocks),
+        }
+    }
+
+    /// 计算结果：公式结果与加密计算页一致，合法 UTF-8 原样使用；
+    /// 原始字节导出时为小写 hex，界面中按显示格式
+    fn compute(&self, pass: &[u8], salt: &[u8], display: DisplayFormat) -> Result<BatchOutput, String> {
+        let bytes = match self {
+            BatchRecipe::Algorithm(algo, params) => digest(*algo, params, pass)?,
+            BatchRecipe::Formula(blocks) => match String::from_utf8(blocks::evaluate(blocks, pass, salt)?) {
+                Ok(text) => return Ok(BatchOutput { hash: text.clone(), display: text, plain: pass.to_vec() }),
+                Err(e) => e.into_bytes(),
+            },
+        };
+        Ok(BatchOutput { hash: hex::encode(&bytes), display: display.format(&bytes), plain: pass.to_vec() })
+    }
+}
+
+pub enum BatchSource {
+    Text(String),
+    File(PathBuf),
+}
+
+pub struct BatchSpec {
+    pub source: BatchSource,
+    /// 每行 `口令<分隔符>盐值`，按最后一个分隔符拆分；None 表示整行都是口令
+    pub salt_separator: Option<String>,
+    pub format: InputFormat,
+    pub recipe: BatchRecipe,
+    /// 原始字节结果的显示格式
+    pub display: DisplayFormat,
+    /// 边计算边写入的 CSV 文件
+    pub output: Option<PathBuf>,
+}
+
+pub struct BatchRow {
+    /// 源文件中的行号 (从 1 开始)
+    pub line: usize,
+    pub input: String,
+    pub salt: Option<String>,
+    pub result: Result<BatchOutput, String>,
+}
+
+pub struct BatchOutput {
+    /// 导出与输出文件使用的结果，不受显示格式影响
+    pub hash: String,
+    /// 界面显示与复制使用的结果
+    pub display: String,
+    /// 实际参与计算的口令字节
+    pub plain: Vec<u8>,
+}
+
+pub enum BatchEvent {
+    Rows(Vec<BatchRow>),
+    Progress { done: u64, total: u64 },
+    Finished { lines: usize, elapsed: Duration, cancelled: bool },
+    Failed(String),
+}
+
+/// 后台批量计算任务，结果分批通过通道送回界面
+pub struct BatchJob {
+    rx: Receiver<BatchEvent>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl BatchJob {
+    pub fn start(spec: BatchSpec) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let flag = cancel.clone();
+        thread::spawn(move || {
+            if let Err(e) = run(spec, &tx, &flag) {
+                let _ = tx.send(BatchEvent::Failed(e));
+            }
+        });
+        Self { rx, cancel }
+    }
+
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    /// 取出目前已到达的全部事件 (不阻塞)
+    pub fn poll(&self) -> Vec<BatchEvent> {
+        self.rx.try_iter().collect()
+    }
+}
+
+/// 字段转换为字节：合法 UTF-8 按输入格式转换，否则 (如 Latin-1 字典) 原样使用
+fn field_bytes(raw: &[u8], format: &InputFormat) -> Result<Vec<u8>, String> {
+    match std::str::from_utf8(raw) {
+        Ok(text) => format.bytes(text),
+        Err(_) => Ok(raw.to_vec()),
+    }
+}
+
+fn split_salt<'a>(line: &'a [u8], separator: &str) -> (&'a [u8], Option<&'a [u8]>) {
+    let sep = separator.as_bytes();
+    if sep.is_empty() || line.len() < sep.len() {
+        return (line, None);
+    }
+    match (0..=line.len() - sep.len()).rev().find(|&i| line[i..].starts_with(sep)) {
+        Some(i) => (&line[..i], Some(&line[i + sep.len()..])),
+        None => (line, None),
+    }
+}
+
+fn process_line(line_no: usize, raw: &[u8], spec: &BatchSpec) -> BatchRow {
+    let (pass, salt) = match &spec.salt_separator {
+        Some(sep) => split_salt(raw, sep),
+        None => (raw, None),
+    };
+    let result = field_bytes(pass, &spec.format).and_then(|pass_bytes| {
+        let salt_bytes = salt.map(|s| field_bytes(s, &spec.format)).transpose()?.unwrap_or_default();
+        spec.recipe.compute(&pass_bytes, &salt_bytes, spec.display)
+    });
+    BatchRow {
+        line: line_no,
+        input: String::from_utf8_lossy(pass).into_owned(),
+        salt: salt.map(|s| String::from_utf8_lossy(s).into_owned()),
+        result,
+    }
+}
+
+fn run(spec: BatchSpec, tx: &Sender<BatchEvent>, cancel: &AtomicBool) -> Result<(), String> {
+    let started = Instant::now();
+    let (mut reader, total): (Box<dyn BufRead>, u64) = match &spec.source {
+        BatchSource::Text(text) => (Box::new(std::io::Cursor::new(text.clone().into_bytes())), text.len() as u64),
+        BatchSource::File(path) => {
+            let file = File::open(path).map_err(|e| format!("无法打开输入文件: {}", e))?;
+            let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+            (Box::new(BufReader::new(file)), len)
+        }
+    };
+    let mut writer = match &spec.output {
+        Some(path) => {
+            let file = File::create(path).map_err(|e| format!("无法创建输出文件: {}", e))?;
+            let mut w = BufWriter::new(file);
+            writeln!(w, "line,input,salt,hash").map_err(|e| e.to_string())?;
+            Some(w)
+        }
+        None => None,
+    };
+
+    let mut buf = Vec::new();
+    let mut chunk = Vec::new();
+    let mut done = 0u64;
+    let mut line_no = 0usize;
+    let mut lines = 0usize;
+    let mut last_flush = Instant::now();
+    loop {
+        if cancel.load(Ordering::Relaxed) {
+            break;
+        }
+        buf.clear();
+        let read = reader.read_until(b'\n', &mut buf).map_err(|e| format!("读取失败: {}", e))?;
+        if read == 0 {
+            break;
+        }
+        done += read as u64;
+        line_no += 1;
+        while buf.last().is_some_and(|b| *b == b'\n' || *b == b'\r') {
+            buf.pop();
+        }
+        if buf.is_empty() {
+            continue;
+        }
+        lines += 1;
+        let row = process_line(line_no, &buf, &spec);
+        if let Some(w) = writer.as_mut() {
+            let hash = match &row.result {
+                Ok(output) => output.hash.clone(),
+                Err(e) => format!("错误: {}", e),
+            };
+            writeln!(
+                w,
+                "{},{},{},{}",
+                row.line,
+                csv_field(&row.input),
+                csv_field(row.salt.as_deref().unwrap_or("")),
+                csv_field(&hash)
+            )
+            .map_err(|e| format!("写入输出文件失败: {}", e))?;
+        }
+        chunk.push(row);
+        if chunk.len() >= CHUNK_ROWS || last_flush.elapsed() > Duration::from_millis(100) {
+            // 界面已关闭时停止计算
+            if tx.send(BatchEvent::Rows(std::mem::take(&mut chunk))).is_err() {
+                return Ok(());
+            }
+            let _ = tx.send(BatchEvent::Progress { done, total });
+            last_flush = Instant::now();
+        }
+    }
+    if let Some(mut w) = writer {
+        w.flush().map_err(|e| format!("写入输出文件失败: {}", e))?;
+    }
+    let _ = tx.send(BatchEvent::Rows(chunk));
+    let _ = tx.send(BatchEvent::Progress { done, total });
+    let _ = tx.send(BatchEvent::Finished {
+        lines,
+        elapsed: started.elapsed(),
+        cancelled: cancel.load(Ordering::Relaxed),
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::InputEncoding;
+
+    fn spec(format: InputFormat, display: DisplayFormat) -> BatchSpec {
+        BatchSpec {
+            source: BatchSource::Text(String::new()),
+            salt_separator: Some(":".to_string()),
+            format,
+            recipe: BatchRecipe::Algorithm(HashAlgorithm::Md5, HashParams::for_algo(HashAlgorithm::Md5)),
+            display,
+            output: None,
+        }
+    }
+
+    #[test]
+    fn export_ignores_display_format() {
+        let row = process_line(1, b"abc", &spec(InputFormat::default(), DisplayFormat::Base64));
+        let output = row.result.unwrap();
+        assert_eq!(output.hash, "900150983cd24fb0d6963f7d28e17f72");
+        assert_eq!(output.display, "kAFQmDzST7DWlj99KOF/cg==");
+    }
+
+    #[test]
+    fn plain_is_hashed_bytes() {
+        let format = InputFormat { encoding: InputEncoding::Hex, escapes: false };
+        let row = process_line(3, b"616263:73", &spec(format, DisplayFormat::LowerHex));
+        assert_eq!(row.input, "616263");
+        assert_eq!(row.salt.as_deref(), Some("73"));
+        let output = row.result.unwrap();
+        assert_eq!(output.plain, b"abc");
+        assert_eq!(output.hash, "900150983cd24fb0d6963f7d28e17f72");
+    }
+}
@@ -0,0 +1,601 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::crypto::{digest, HashAlgorithm, HashParams, InputEncoding, OutputEncoding};
+
+#[derive(Clone, PartialEq, Eq)]
+pub enum CustomBlock {
+    Password,
+    Salt,
+    Literal(String),
+    Hash {
+        algo: HashAlgorithm,
+        params: HashParams,
+        encoding: OutputEncoding,
+        inner: Vec<CustomBlock>,
+    },
+    /// 对内部结果重复计算 `rounds` 轮，例如 md5^1000($pass)
+    Repeat {
+        algo: HashAlgorithm,
+        params: HashParams,
+        encoding: OutputEncoding,
+        rounds: u32,
+        rejoin: Rejoin,
+        inner: Vec<CustomBlock>,
+    },
+}
+
+/// 循环块每轮计算前对上一轮结果的重新拼接方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rejoin {
+    None,
+    PrependPass,
+    AppendPass,
+    PrependSalt,
+    AppendSalt,
+}
+
+impl Rejoin {
+    pub fn all() -> &'static [Rejoin] {
+        &[
+            Rejoin::None,
+            Rejoin::PrependPass,
+            Rejoin::AppendPass,
+            Rejoin::PrependSalt,
+            Rejoin::AppendSalt,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rejoin::None => "不拼接",
+            Rejoin::PrependPass => "$pass + 上轮结果",
+            Rejoin::AppendPass => "上轮结果 + $pass",
+            Rejoin::PrependSalt => "$salt + 上轮结果",
+            Rejoin::AppendSalt => "上轮结果 + $salt",
+        }
+    }
+
+    /// 公式预览中使用的短标记
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Rejoin::None => "-",
+            Rejoin::PrependPass => "$pass+",
+            Rejoin::AppendPass => "+$pass",
+            Rejoin::PrependSalt => "$salt+",
+            Rejoin::AppendSalt => "+$salt",
+        }
+    }
+
+    fn apply(&self, buf: &mut Vec<u8>, prev: &[u8], pass: &[u8], salt: &[u8]) {
+        buf.clear();
+        match self {
+            Rejoin::None => buf.extend_from_slice(prev),
+            Rejoin::PrependPass => {
+                buf.extend_from_slice(pass);
+                buf.extend_from_slice(prev);
+            }
+            Rejoin::AppendPass => {
+                buf.extend_from_slice(prev);
+                buf.extend_from_slice(pass);
+            }
+            Rejoin::PrependSalt => {
+                buf.extend_from_slice(salt);
+                buf.extend_from_slice(prev);
+            }
+            Rejoin::AppendSalt => {
+                buf.extend_from_slice(prev);
+                buf.extend_from_slice(salt);
+            }
+        }
+    }
+}
+
+impl CustomBlock {
+    /// 新建哈希积木，默认以小写 hex 输出 (与旧版本行为一致)；
+    /// 明文块默认原样透传。
+    pub fn hash(algo: HashAlgorithm) -> Self {
+        CustomBlock::Hash {
+            algo,
+            params: HashParams::for_algo(algo),
+            encoding: default_encoding(algo),
+            inner: vec![],
+        }
+    }
+
+    pub fn repeat(algo: HashAlgorithm) -> Self {
+        CustomBlock::Repeat {
+            algo,
+            params: HashParams::for_algo(algo),
+            encoding: default_encoding(algo),
+            rounds: 2,
+            rejoin: Rejoin::None,
+            inner: vec![],
+        }
+    }
+
+    pub fn children(&self) -> Option<&Vec<CustomBlock>> {
+        match self {
+            CustomBlock::Hash { inner, .. } | CustomBlock::Repeat { inner, .. } => Some(inner),
+            _ => None,
+        }
+    }
+
+    pub fn children_mut(&mut self) -> Option<&mut Vec<CustomBlock>> {
+        match self {
+            CustomBlock::Hash { inner, .. } | CustomBlock::Repeat { inner, .. } => Some(inner),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            CustomBlock::Password => "$pass".to_string(),
+            CustomBlock::Salt => "$salt".to_string(),
+            CustomBlock::Literal(s) => format!("\"{}\"", s),
+            CustomBlock::Hash { algo, .. } => format!("{}(...)", algo.name()),
+            CustomBlock::Repeat { algo, rounds, .. } => format!("{}^{}(...)", algo.name(), rounds),
+        }
+    }
+}
+
+/// 按路径取积木：路径中每一项是所在列表中的下标
+pub fn block_at<'a>(blocks: &'a [CustomBlock], path: &[usize]) -> Option<&'a CustomBlock> {
+    let (first, rest) = path.split_first()?;
+    let block = blocks.get(*first)?;
+    if rest.is_empty() {
+        Some(block)
+    } else {
+        block_at(block.children()?, rest)
+    }
+}
+
+/// 按路径取子积木列表，空路径表示根列表
+fn list_at_mut<'a>(blocks: &'a mut Vec<CustomBlock>, path: &[usize]) -> Option<&'a mut Vec<CustomBlock>> {
+    match path.split_first() {
+        None => Some(blocks),
+        Some((first, rest)) => list_at_mut(blocks.get_mut(*first)?.children_mut()?, rest),
+    }
+}
+
+/// 在 `list` 列表的 `index` 位置插入积木
+pub fn insert_block(blocks: &mut Vec<CustomBlock>, list: &[usize], index: usize, block: CustomBlock) -> bool {
+    match list_at_mut(blocks, list) {
+        Some(target) => {
+            target.insert(index.min(target.len()), block);
+            true
+        }
+        None => false,
+    }
+}
+
+/// 将 `from` 处的积木移动到 `to_list` 列表的 `to_index` 位置 (插入到该下标之前)。
+/// 目标位于源积木内部或位置不变时返回 false。
+pub fn move_block(blocks: &mut Vec<CustomBlock>, from: &[usize], to_list: &[usize], to_index: usize) -> bool {
+    let Some((&from_index, from_list)) = from.split_last() else { return false };
+    if to_list.starts_with(from) || block_at(blocks, from).is_none() {
+        return false;
+    }
+    if !to_list.is_empty() && block_at(blocks, to_list).and_then(CustomBlock::children).is_none() {
+        return false;
+    }
+    if to_list == from_list && (to_index == from_index || to_index == from_index + 1) {
+        return false;
+    }
+
+    // 移除源积木后，同一列表中排在它后面的下标都会前移一位
+    let mut to_list = to_list.to_vec();
+    let mut to_index = to_index;
+    if to_list.len() > from_list.len() && to_list.starts_with(from_list) {
+        let depth = from_list.len();
+        if to_list[depth] > from_index {
+            to_list[depth] -= 1;
+        }
+    } else if to_list == from_list && to_index > from_index {
+        to_index -= 1;
+    }
+
+    let Some(source) = list_at_mut(blocks, from_list) else { return false };
+    let block = source.remove(from_index);
+    insert_block(blocks, &to_list, to_index, block)
+}
+
+fn default_encoding(algo: HashAlgorithm) -> OutputEncoding {
+    if algo == HashAlgorithm::None {
+        OutputEncoding::Raw
+    } else {
+        OutputEncoding::LowerHex
+    }
+}
+
+fn algo_label(algo: HashAlgorithm, params: &HashParams, encoding: OutputEncoding) -> String {
+    let name = format!("{}{}", algo.name(), params.tag(algo));
+    if encoding == default_encoding(algo) {
+        name
+    } else {
+        format!("{}:{}", name, encoding.tag())
+    }
+}
+
+/// 生成公式预览文本。非默认编码会以 `算法:编码(...)` 的形式标出。
+pub fn formula(blocks: &[CustomBlock]) -> String {
+    let mut parts = Vec::new();
+    for block in blocks {
+        match block {
+            CustomBlock::Password => parts.push("$pass".to_string()),
+            CustomBlock::Salt => parts.push("$salt".to_string()),
+            CustomBlock::Literal(l) => parts.push(format!("\"{}\"", l)),
+            CustomBlock::Hash { algo, params, encoding, inner } => {
+                parts.push(format!("{}({})", algo_label(*algo, params, *encoding), formula(inner)));
+            }
+            CustomBlock::Repeat { algo, params, encoding, rounds, rejoin, inner } => {
+                let rejoin = if *rejoin == Rejoin::None {
+                    String::new()
+                } else {
+                    format!("[{}]", rejoin.tag())
+                };
+                parts.push(format!("{}^{}{}({})", algo_label(*algo, params, *encoding), rounds, rejoin, formula(inner)));
+            }
+        }
+    }
+    parts.join(" + ")
+}
+
+/// 内置配方标签 (加密计算列表、推算结果中的 `md5`、`sha3_256` 等) 中的算法名
+pub fn recipe_algo(name: &str) -> Option<HashAlgorithm> {
+    let algo = match name {
+        "md5" => HashAlgorithm::Md5,
+        "sha1" => HashAlgorithm::Sha1,
+        "sha256" => HashAlgorithm::Sha256,
+        "sha384" => HashAlgorithm::Sha384,
+        "sha512" => HashAlgorithm::Sha512,
+        "sha512_224" => HashAlgorithm::Sha512_224,
+        "sha512_256" => HashAlgorithm::Sha512_256,
+        "sha3_256" => HashAlgorithm::Sha3_256,
+        "sha3_512" => HashAlgorithm::Sha3_512,
+        "keccak256" => HashAlgorithm::Keccak256,
+        "keccak512" => HashAlgorithm::Keccak512,
+        "sm3" => HashAlgorithm::Sm3,
+        "streebog256" => HashAlgorithm::Streebog256,
+        "streebog512" => HashAlgorithm::Streebog512,
+        "gost" => HashAlgorithm::Gost94,
+        "gost_cryptopro" => HashAlgorithm::Gost94CryptoPro,
+        "ripemd128" => HashAlgorithm::Ripemd128,
+        "ripemd160" => HashAlgorithm::Ripemd160,
+        "ripemd256" => HashAlgorithm::Ripemd256,
+        "ripemd320" => HashAlgorithm::Ripemd320,
+        "whirlpool" => HashAlgorithm::Whirlpool,
+        "tiger" => HashAlgorithm::Tiger,
+        "md2" => HashAlgorithm::Md2,
+        "blake2b" => HashAlgorithm::Blake2b,
+        "blake2s" => HashAlgorithm::Blake2s,
+        "blake3" => HashAlgorithm::Blake3,
+        "shake128" => HashAlgorithm::Shake128,
+        "shake256" => HashAlgorithm::Shake256,
+        "cshake128" => HashAlgorithm::CShake128,
+        "cshake256" => HashAlgorithm::CShake256,
+        "k12" => HashAlgorithm::KangarooTwelve,
+        "blake3_xof" => HashAlgorithm::Blake3Xof,
+        "crc32" => HashAlgorithm::Crc32,
+        "crc32c" => HashAlgorithm::Crc32c,
+        "crc64_xz" => HashAlgorithm::Crc64,
+        "adler32" => HashAlgorithm::Adler32,
+        "xxh32" => HashAlgorithm::XxHash32,
+        "xxh64" => HashAlgorithm::XxHash64,
+        "xxh3_64" => HashAlgorithm::Xxh3_64,
+        "murmur3_32" => HashAlgorithm::Murmur3_32,
+        "fnv1_32" => HashAlgorithm::Fnv132,
+        "fnv1a_32" => HashAlgorithm::Fnv1a32,
+        "fnv1_64" => HashAlgorithm::Fnv164,
+        "fnv1a_64" => HashAlgorithm::Fnv1a64,
+        "siphash24" => HashAlgorithm::SipHash24,
+        _ => return None,
+    };
+    Some(algo)
+}
+
+/// 配方中的哈希积木：XOF 与校验和沿用列表计算时的参数，其余为标准用法
+fn recipe_hash(algo: HashAlgorithm, xof: &HashParams, checksum: &HashParams, inner: Vec<CustomBlock>) -> CustomBlock {
+    let params = if algo.is_xof() {
+        xof.clone()
+    } else if algo.is_checksum() {
+        checksum.clone()
+    } else {
+        HashParams::for_algo(algo)
+    };
+    CustomBlock::Hash { algo, params, encoding: OutputEncoding::LowerHex, inner }
+}
+
+/// 解析 `项.项...`，项为 `$pass`、`$salt` 或 `算法(...)`，返回积木与未解析的剩余部分
+fn parse_recipe_concat<'a>(text: &'a str, xof: &HashParams, checksum: &HashParams) -> Option<(Vec<CustomBlock>, &'a str)> {
+    let mut blocks = Vec::new();
+    let mut rest = text;
+    loop {
+        let (block, after) = parse_recipe_term(rest, xof, checksum)?;
+        blocks.push(block);
+        match after.strip_prefix('.') {
+            Some(next) => rest = next,
+            None => return Some((blocks, after)),
+        }
+    }
+}
+
+fn parse_recipe_term<'a>(text: &'a str, xof: &HashParams, checksum: &HashParams) -> Option<(CustomBlock, &'a str)> {
+    if let Some(rest) = text.strip_prefix("$pass") {
+        return Some((CustomBlock::Password, rest));
+    }
+    if let Some(rest) = text.strip_prefix("$salt") {
+        return Some((CustomBlock::Salt, rest));
+    }
+    let open = text.find('(')?;
+    let algo = recipe_algo(&text[..open])?;
+    let (inner, rest) = parse_recipe_concat(&text[open + 1..], xof, checksum)?;
+    Some((recipe_hash(algo, xof, checksum, inner), rest.strip_prefix(')')?))
+}
+
+/// 将内置配方标签 (如 `md5($salt.md5($pass))`) 转换为等价的积木链。
+/// md5(unicode) 还需把口令按 UTF-16LE 编码 (见 `recipe_input_encoding`)。
+/// 依赖 UTF-16 字符处理、截断或特定格式的配方 (ntlm、lm、md5_middle、LDAP、apr1 等) 无法用积木表示，返回 None
+pub fn from_recipe(label: &str, xof: &HashParams, checksum: &HashParams) -> Option<Vec<CustomBlock>> {
+    if let Some(algo) = recipe_algo(label) {
+        return Some(vec![recipe_hash(algo, xof, checksum, vec![CustomBlock::Password])]);
+    }
+    let base64 = || CustomBlock::Hash {
+        algo: HashAlgorithm::None,
+        params: HashParams::default(),
+        encoding: OutputEncoding::Base64,
+        inner: vec![CustomBlock::Password],
+    };
+    match label {
+        "base64" => return Some(vec![base64()]),
+        "md5(base64)" => return Some(vec![recipe_hash(HashAlgorithm::Md5, xof, checksum, vec![base64()])]),
+        "md5(unicode)" => return Some(vec![recipe_hash(HashAlgorithm::Md5, xof, checksum, vec![CustomBlock::Password])]),
+        _ => {}
+    }
+    if label == "mysql5" {
+        // "*" + 大写 hex(sha1(sha1 原始字节))
+        let inner = CustomBlock::Hash {
+            algo: HashAlgorithm::Sha1,
+            params: HashParams::default(),
+            encoding: OutputEncoding::Raw,
+            inner: vec![CustomBlock::Password],
+        };
+        let outer = CustomBlock::Hash {
+            algo: HashAlgorithm::Sha1,
+            params: HashParams::default(),
+            encoding: OutputEncoding::UpperHex,
+            inner: vec![inner],
+        };
+        return Some(vec![CustomBlock::Literal("*".to_string()), outer]);
+    }
+    match parse_recipe_concat(label, xof, checksum)? {
+        (blocks, "") => Some(blocks),
+        _ => None,
+    }
+}
+
+/// 配方要求的口令编码，None 表示沿用推算时的字符集
+pub fn recipe_input_encoding(label: &str) -> Option<InputEncoding> {
+    (label == "md5(unicode)").then_some(InputEncoding::Utf16Le)
+}
+
+/// 检查所有积木的算法参数，返回第一个错误
+pub fn validate(blocks: &[CustomBlock]) -> Result<(), String> {
+    for block in blocks {
+        // 循环 "无" 算法没有意义，且带拼接时每轮都会变长，轮数较大时内存与耗时按平方增长
+        if let CustomBlock::Repeat { algo: HashAlgorithm::None, .. } = block {
+            return Err("循环积木不能使用 \"无\" 算法，请选择一个哈希算法".to_string());
+        }
+        if let CustomBlock::Hash { algo, params, inner, .. } | CustomBlock::Repeat { algo, params, inner, .. } = block {
+            params.validate(*algo)?;
+            validate(inner)?;
+        }
+    }
+    Ok(())
+}
+
+/// 按字节计算积木链，内层哈希按各自的输出编码拼接到外层输入中。
+/// 算法参数不合法时返回错误 (可先用 `validate` 检查)。
+pub fn evaluate(blocks: &[CustomBlock], pass: &[u8], salt: &[u8]) -> Result<Vec<u8>, String> {
+    evaluate_inner(blocks, pass, salt, None, &AtomicBool::new(false))
+}
+
+/// 循环块在追踪中最多展开的轮数 (另外总会保留最后一轮)
+const TRACE_ROUND_LIMIT: u32 = 16;
+
+/// 计算过程中单个积木 (或循环块的一轮) 的输入与输出
+pub struct TraceNode {
+    pub label: String,
+    /// 送入哈希函数的字节；明文块没有输入
+    pub input: Option<Vec<u8>>,
+    pub output: Vec<u8>,
+    pub children: Vec<TraceNode>,
+}
+
+/// 与 `evaluate` 相同的计算，同时记录每一步的中间结果。最外层各节点输出依次拼接即为 `evaluate` 的结果。
+/// `cancel` 置位后尽快返回，此时结果不完整，由调用方丢弃
+pub fn trace(blocks: &[CustomBlock], pass: &[u8], salt: &[u8], cancel: &AtomicBool) -> Result<Vec<TraceNode>, String> {
+    let mut nodes = Vec::new();
+    evaluate_inner(blocks, pass, salt, Some(&mut nodes), cancel)?;
+    Ok(nodes)
+}
+
+fn evaluate_inner(
+    blocks: &[CustomBlock],
+    pass: &[u8],
+    salt: &[u8],
+    mut trace: Option<&mut Vec<TraceNode>>,
+    cancel: &AtomicBool,
+) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    for block in blocks {
+        let mut children = Vec::new();
+        let mut input = None;
+        let value = match block {
+            CustomBlock::Password => pass.to_vec(),
+            CustomBlock::Salt => salt.to_vec(),
+            CustomBlock::Literal(l) => l.as_bytes().to_vec(),
+            CustomBlock::Hash { algo, params, encoding, inner } => {
+                let inner_content = evaluate_inner(inner, pass, salt, trace.is_some().then_some(&mut children), cancel)?;
+                let value = encoding.encode(&digest(*algo, params, &inner_content)?);
+                input = Some(inner_content);
+                value
+            }
+            CustomBlock::Repeat { algo, params, encoding, rounds, rejoin, inner } => {
+                // 迭代而非递归，轮数很大时也不会爆栈
+                let mut value = evaluate_inner(inner, pass, salt, trace.is_some().then_some(&mut children), cancel)?;
+                if trace.is_some() {
+                    input = Some(value.clone());
+                }
+                let mut buf = Vec::new();
+                for round in 1..=*rounds {
+                    if cancel.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    rejoin.apply(&mut buf, &value, pass, salt);
+                    value = encoding.encode(&digest(*algo, params, &buf)?);
+                    if trace.is_some() && (round <= TRACE_ROUND_LIMIT || round == *rounds) {
+                        let label = if round > TRACE_ROUND_LIMIT + 1 {
+                            format!("第 {} 轮 (已省略第 {}-{} 轮)", round, TRACE_ROUND_LIMIT + 1, round - 1)
+                        } else {
+                            format!("第 {} 轮", round)
+                        };
+                        children.push(TraceNode {
+                            label,
+                            input: Some(buf.clone()),
+                            output: value.clone(),
+                            children: Vec::new(),
+                        });
+                    }
+                }
+                value
+            }
+        };
+        if let Some(nodes) = trace.as_deref_mut() {
+            let label = formula(std::slice::from_ref(block));
+            nodes.push(TraceNode { label, input, output: value.clone(), children });
+        }
+        out.extend(value);
+    }
+    Ok(out)
+}
+
+const HISTORY_LIMIT: usize = 100;
+/// 连续的修改/移动在该时间窗口内合并为一条历史 (拖动数值、拖拽排序时每帧都会变化)
+const HISTORY_MERGE_SECS: f64 = 1.0;
+
+const CHANGE_EDIT: &str = "修改/移动积木";
+
+struct HistoryEntry {
+    label: &'static str,
+    blocks: Vec<CustomBlock>,
+}
+
+/// 积木区的撤销/重做栈，每条记录保存修改前的完整快照
+#[derive(Default)]
+pub struct BlockHistory {
+    undo: Vec<HistoryEntry>,
+    redo: Vec<HistoryEntry>,
+    last_change: Option<(&'static str, f64)>,
+}
+
+impl BlockHistory {
+    /// 记录一次修改：`before` 为修改前的积木，`after` 为修改后的积木
+    pub fn record(&mut self, before: Vec<CustomBlock>, after: &[CustomBlock], now: f64) {
+        let label = describe_change(&before, after);
+        self.redo.clear();
+        if let Some((last_label, last_time)) = self.last_change {
+            if label == CHANGE_EDIT && last_label == label && now - last_time < HISTORY_MERGE_SECS && !self.undo.is_empty() {
+                self.last_change = Some((label, now));
+                return;
+            }
+        }
+        self.undo.push(HistoryEntry { label, blocks: before });
+        if self.undo.len() > HISTORY_LIMIT {
+            self.undo.remove(0);
+        }
+        self.last_change = Some((label, now));
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn undo(&mut self, current: &mut Vec<CustomBlock>) -> bool {
+        let Some(entry) = self.undo.pop() else { return false };
+        let prev = std::mem::replace(current, entry.blocks);
+        self.redo.push(HistoryEntry { label: entry.label, blocks: prev });
+        self.last_change = None;
+        true
+    }
+
+    pub fn redo(&mut self, current: &mut Vec<CustomBlock>) -> bool {
+        let Some(entry) = self.redo.pop() else { return false };
+        let prev = std::mem::replace(current, entry.blocks);
+        self.undo.push(HistoryEntry { label: entry.label, blocks: prev });
+        self.last_change = None;
+        true
+    }
+
+    /// 已执行的修改 (从旧到新)
+    pub fn done_labels(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.undo.iter().map(|e| e.label)
+    }
+
+    /// 可重做的修改 (从近到远)
+    pub fn undone_labels(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.redo.iter().rev().map(|e| e.label)
+    }
+}
+
+fn count_blocks(blocks: &[CustomBlock]) -> usize {
+    blocks
+        .iter()
+        .map(|b| 1 + b.children().map_or(0, |inner| count_blocks(inner)))
+        .sum()
+}
+
+fn describe_change(before: &[CustomBlock], after: &[CustomBlock]) -> &'static str {
+    let (old, new) = (count_blocks(before), count_blocks(after));
+    if new == 0 && old > 1 {
+        "清空积木"
+    } else if new > old {
+        "添加积木"
+    } else if new < old {
+        "删除积木"
+    } else {
+        CHANGE_EDIT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::calculate_complex_hashes;
+
+    #[test]
+    fn from_recipe_matches_bulk_results() {
+        let xof = HashParams::for_algo(HashAlgorithm::Shake256);
+        let checksum = HashParams::default();
+        let (text, salt) = ("p@ss密码", "s4lt");
+        let mut unsupported = Vec::new();
+        for (label, hash) in calculate_complex_hashes(text.as_bytes(), Some(text), salt.as_bytes(), &xof) {
+            let Some(blocks) = from_recipe(&label, &xof, &checksum) else {
+                unsupported.push(label);
+                continue;
+            };
+            let pass = recipe_input_encoding(&label).unwrap_or(InputEncoding::Utf8).encode_text(text).unwrap();
+            let output = evaluate(&blocks, &pass, salt.as_bytes()).unwrap();
+            assert_eq!(String::from_utf8(output).unwrap(), hash, "{}", label);
+        }
+        assert!(!unsupported.iter().any(|l| l == "md5(unicode)" || l == "md5(base64)"));
+        for label in &unsupported {
+            let expected = ["ntlm", "lm", "md5_middle", "md5(substring(md5($pass),8,16))", "apr1"].contains(&label.as_str())
+                || label.starts_with("ldap_");
+            assert!(expected, "{}", label);
+        }
+    }
+}
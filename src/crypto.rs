@@ -1,14 +1,22 @@
 use md5::Md5;
 use md4::Md4;
 use sha1::Sha1;
-use sha2::{Sha224, Sha256, Sha384, Sha512};
-use sha3::{Sha3_224, Sha3_256, Sha3_384, Sha3_512};
+use sha2::{Sha224, Sha256, Sha384, Sha512, Sha512_224, Sha512_256};
+use sha3::{CShake128, CShake128Core, CShake256, CShake256Core, Keccak256, Keccak512, Sha3_224, Sha3_256, Sha3_384, Sha3_512, Shake128, Shake256};
 use sm3::Sm3;
-use ripemd::Ripemd160;
+use ripemd::{Ripemd128, Ripemd160, Ripemd256, Ripemd320};
+use streebog::{Streebog256, Streebog512};
+use gost94::{Gost94CryptoPro, Gost94Test};
+use tiger::Tiger;
+use md2::Md2;
 use whirlpool::Whirlpool;
 use blake2::{Blake2b512, Blake2s256};
-use digest::Digest;
+use digest::{Digest, ExtendableOutput};
+use k12::{KangarooTwelve, KangarooTwelveCore};
 use base64::prelude::*;
+use crc::{Crc, CRC_32_ISCSI, CRC_32_ISO_HDLC, CRC_64_XZ};
+use xxhash_rust::{xxh3::xxh3_64_with_seed, xxh32::xxh32, xxh64::xxh64};
+use siphasher::sip::SipHasher24;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HashAlgorithm {
@@ -19,16 +27,50 @@ pub enum HashAlgorithm {
     Sha256,
     Sha384,
     Sha512,
+    Sha512_224,
+    Sha512_256,
     Sha3_224,
     Sha3_256,
     Sha3_384,
     Sha3_512,
+    Keccak256, // 以太坊使用的原始 Keccak (填充方式与 SHA3 不同)
+    Keccak512,
     Sm3,
+    Streebog256, // GOST R 34.11-2012
+    Streebog512,
+    Gost94,          // GOST R 34.11-94, 测试参数集 (PHP 'gost')
+    Gost94CryptoPro, // GOST R 34.11-94, CryptoPro 参数集 (PHP 'gost-crypto')
+    Ripemd128,
     Ripemd160,
+    Ripemd256,
+    Ripemd320,
     Whirlpool,
+    Tiger,
+    Md2,
     Blake2b,
     Blake2s,
     Blake3,
+    // 可变输出长度 (XOF)
+    Shake128,
+    Shake256,
+    CShake128,
+    CShake256,
+    KangarooTwelve,
+    Blake3Xof,
+    // 非加密校验和 (结果按整数值大端输出)
+    Crc32,
+    Crc32c,
+    Crc64, // CRC-64/XZ (与 Go crc64.ECMA 相同)
+    Adler32,
+    XxHash32,
+    XxHash64,
+    Xxh3_64,
+    Murmur3_32,
+    Fnv132,
+    Fnv1a32,
+    Fnv164,
+    Fnv1a64,
+    SipHash24,
 }
 
 impl HashAlgorithm {
@@ -41,16 +83,48 @@ impl HashAlgorithm {
             HashAlgorithm::Sha256,
             HashAlgorithm::Sha384,
             HashAlgorithm::Sha512,
+            HashAlgorithm::Sha512_224,
+            HashAlgorithm::Sha512_256,
             HashAlgorithm::Sha3_224,
             HashAlgorithm::Sha3_256,
             HashAlgorithm::Sha3_384,
             HashAlgorithm::Sha3_512,
+            HashAlgorithm::Keccak256,
+            HashAlgorithm::Keccak512,
             HashAlgorithm::Sm3,
+            HashAlgorithm::Streebog256,
+            HashAlgorithm::Streebog512,
+            HashAlgorithm::Gost94,
+            HashAlgorithm::Gost94CryptoPro,
+            HashAlgorithm::Ripemd128,
             HashAlgorithm::Ripemd160,
+            HashAlgorithm::Ripemd256,
+            HashAlgorithm::Ripemd320,
             HashAlgorithm::Whirlpool,
+            HashAlgorithm::Tiger,
+            HashAlgorithm::Md2,
             HashAlgorithm::Blake2b,
             HashAlgorithm::Blake2s,
             HashAlgorithm::Blake3,
+            HashAlgorithm::Shake128,
+            HashAlgorithm::Shake256,
+            HashAlgorithm::CShake128,
+            HashAlgorithm::CShake256,
+            HashAlgorithm::KangarooTwelve,
+            HashAlgorithm::Blake3Xof,
+            HashAlgorithm::Crc32,
+            HashAlgorithm::Crc32c,
+            HashAlgorithm::Crc64,
+            HashAlgorithm::Adler32,
+            HashAlgorithm::XxHash32,
+            HashAlgorithm::XxHash64,
+            HashAlgorithm::Xxh3_64,
+            HashAlgorithm::Murmur3_32,
+            HashAlgorithm::Fnv132,
+            HashAlgorithm::Fnv1a32,
+            HashAlgorithm::Fnv164,
+            HashAlgorithm::Fnv1a64,
+            HashAlgorithm::SipHash24,
         ]
     }
 
@@ -63,154 +137,917 @@ impl HashAlgorithm {
             HashAlgorithm::Sha256 => "SHA2-256",
             HashAlgorithm::Sha384 => "SHA2-384",
             HashAlgorithm::Sha512 => "SHA2-512",
+            HashAlgorithm::Sha512_224 => "SHA2-512/224",
+            HashAlgorithm::Sha512_256 => "SHA2-512/256",
             HashAlgorithm::Sha3_224 => "SHA3-224",
             HashAlgorithm::Sha3_256 => "SHA3-256",
             HashAlgorithm::Sha3_384 => "SHA3-384",
             HashAlgorithm::Sha3_512 => "SHA3-512",
+            HashAlgorithm::Keccak256 => "Keccak-256",
+            HashAlgorithm::Keccak512 => "Keccak-512",
             HashAlgorithm::Sm3 => "SM3",
+            HashAlgorithm::Streebog256 => "Streebog-256",
+            HashAlgorithm::Streebog512 => "Streebog-512",
+            HashAlgorithm::Gost94 => "GOST R 34.11-94",
+            HashAlgorithm::Gost94CryptoPro => "GOST R 34.11-94 (CryptoPro)",
+            HashAlgorithm::Ripemd128 => "RIPEMD-128",
             HashAlgorithm::Ripemd160 => "RIPEMD-160",
+            HashAlgorithm::Ripemd256 => "RIPEMD-256",
+            HashAlgorithm::Ripemd320 => "RIPEMD-320",
             HashAlgorithm::Whirlpool => "Whirlpool",
+            HashAlgorithm::Tiger => "Tiger",
+            HashAlgorithm::Md2 => "MD2",
             HashAlgorithm::Blake2b => "BLAKE2b",
             HashAlgorithm::Blake2s => "BLAKE2s",
             HashAlgorithm::Blake3 => "BLAKE3",
+            HashAlgorithm::Shake128 => "SHAKE128",
+            HashAlgorithm::Shake256 => "SHAKE256",
+            HashAlgorithm::CShake128 => "cSHAKE128",
+            HashAlgorithm::CShake256 => "cSHAKE256",
+            HashAlgorithm::KangarooTwelve => "KangarooTwelve",
+            HashAlgorithm::Blake3Xof => "BLAKE3 XOF",
+            HashAlgorithm::Crc32 => "CRC32",
+            HashAlgorithm::Crc32c => "CRC32C",
+            HashAlgorithm::Crc64 => "CRC64 (XZ)",
+            HashAlgorithm::Adler32 => "Adler-32",
+            HashAlgorithm::XxHash32 => "xxHash32",
+            HashAlgorithm::XxHash64 => "xxHash64",
+            HashAlgorithm::Xxh3_64 => "XXH3-64",
+            HashAlgorithm::Murmur3_32 => "MurmurHash3 (x86_32)",
+            HashAlgorithm::Fnv132 => "FNV-1 32",
+            HashAlgorithm::Fnv1a32 => "FNV-1a 32",
+            HashAlgorithm::Fnv164 => "FNV-1 64",
+            HashAlgorithm::Fnv1a64 => "FNV-1a 64",
+            HashAlgorithm::SipHash24 => "SipHash-2-4",
+        }
+    }
+
+    /// 输出长度可任意指定的算法 (XOF)
+    pub fn is_xof(&self) -> bool {
+        matches!(
+            self,
+            HashAlgorithm::Shake128
+                | HashAlgorithm::Shake256
+                | HashAlgorithm::CShake128
+                | HashAlgorithm::CShake256
+                | HashAlgorithm::KangarooTwelve
+                | HashAlgorithm::Blake3Xof
+        )
+    }
+
+    /// 支持自定义串 (customization string) 的算法
+    pub fn has_customization(&self) -> bool {
+        matches!(self, HashAlgorithm::CShake128 | HashAlgorithm::CShake256 | HashAlgorithm::KangarooTwelve)
+    }
+
+    pub fn is_blake2(&self) -> bool {
+        matches!(self, HashAlgorithm::Blake2b | HashAlgorithm::Blake2s)
+    }
+
+    pub fn is_blake3(&self) -> bool {
+        matches!(self, HashAlgorithm::Blake3 | HashAlgorithm::Blake3Xof)
+    }
+
+    /// 非加密校验和 / 哈希表用哈希
+    pub fn is_checksum(&self) -> bool {
+        matches!(
+            self,
+            HashAlgorithm::Crc32
+                | HashAlgorithm::Crc32c
+                | HashAlgorithm::Crc64
+                | HashAlgorithm::Adler32
+                | HashAlgorithm::XxHash32
+                | HashAlgorithm::XxHash64
+                | HashAlgorithm::Xxh3_64
+                | HashAlgorithm::Murmur3_32
+                | HashAlgorithm::Fnv132
+                | HashAlgorithm::Fnv1a32
+                | HashAlgorithm::Fnv164
+                | HashAlgorithm::Fnv1a64
+                | HashAlgorithm::SipHash24
+        )
+    }
+
+    /// 未加盐、默认参数时对应的 hashcat 模式号
+    pub fn hashcat_mode(&self) -> Option<u32> {
+        let mode = match self {
+            HashAlgorithm::Md5 => 0,
+            HashAlgorithm::Sha1 => 100,
+            HashAlgorithm::Sha224 => 1300,
+            HashAlgorithm::Sha256 => 1400,
+            HashAlgorithm::Sha512 => 1700,
+            HashAlgorithm::Ripemd160 => 6000,
+            HashAlgorithm::Whirlpool => 6100,
+            HashAlgorithm::Gost94 => 6900,
+            HashAlgorithm::Sha384 => 10800,
+            HashAlgorithm::Streebog256 => 11700,
+            HashAlgorithm::Streebog512 => 11800,
+            HashAlgorithm::Sha3_224 => 17300,
+            HashAlgorithm::Sha3_256 => 17400,
+            HashAlgorithm::Sha3_384 => 17500,
+            HashAlgorithm::Sha3_512 => 17600,
+            HashAlgorithm::Keccak256 => 17800,
+            HashAlgorithm::Keccak512 => 18000,
+            HashAlgorithm::Sm3 => 31100,
+            _ => return None,
+        };
+        Some(mode)
+    }
+
+    /// 支持种子 (seed) 的算法及种子的最大值
+    pub fn max_seed(&self) -> Option<u64> {
+        match self {
+            HashAlgorithm::XxHash32 | HashAlgorithm::Murmur3_32 => Some(u32::MAX as u64),
+            HashAlgorithm::XxHash64 | HashAlgorithm::Xxh3_64 => Some(u64::MAX),
+            _ => None,
+        }
+    }
+
+    /// 可指定输出长度时的最大长度 (字节)
+    pub fn max_output_len(&self) -> Option<usize> {
+        match self {
+            HashAlgorithm::Blake2b => Some(64),
+            HashAlgorithm::Blake2s => Some(32),
+            algo if algo.is_xof() => Some(XOF_MAX_LEN),
+            _ => None,
+        }
+    }
+
+    /// BLAKE2 的 salt / personalization 最大长度 (字节)
+    fn blake2_param_len(&self) -> usize {
+        if *self == HashAlgorithm::Blake2b {
+            16
+        } else {
+            8
+        }
+    }
+}
+
+/// 算法参数。每个算法只使用其中与自身相关的字段，其余字段忽略。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HashParams {
+    /// 输出字节数 (XOF 与 BLAKE2)
+    pub output_len: usize,
+    /// cSHAKE / KangarooTwelve 的自定义串
+    pub customization: String,
+    /// BLAKE2 / BLAKE3 / SipHash 的密钥，BLAKE3 要求恰好 32 字节，SipHash 为 16 字节
+    pub key: String,
+    /// BLAKE2 salt
+    pub salt: String,
+    /// BLAKE2 personalization
+    pub personalization: String,
+    /// BLAKE3 derive_key 的 context 串
+    pub context: String,
+    /// xxHash / MurmurHash3 的种子
+    pub seed: u64,
+}
+
+/// 界面上允许的最大输出长度 (字节)
+pub const XOF_MAX_LEN: usize = 4096;
+
+impl Default for HashParams {
+    fn default() -> Self {
+        Self {
+            output_len: 32,
+            customization: String::new(),
+            key: String::new(),
+            salt: String::new(),
+            personalization: String::new(),
+            context: String::new(),
+            seed: 0,
         }
     }
 }
 
+impl HashParams {
+    /// 新建积木时的默认参数，BLAKE2 默认输出完整长度
+    pub fn for_algo(algo: HashAlgorithm) -> Self {
+        let mut params = Self::default();
+        if algo.is_blake2() {
+            params.output_len = algo.max_output_len().unwrap_or(32);
+        }
+        params
+    }
+
+    /// 是否与该算法的标准 (无密钥、默认长度) 用法一致
+    pub fn is_standard(&self, algo: HashAlgorithm) -> bool {
+        if algo.is_blake2() {
+            Some(self.output_len) == algo.max_output_len()
+                && self.key.is_empty()
+                && self.salt.is_empty()
+                && self.personalization.is_empty()
+        } else if algo.is_blake3() {
+            self.key.is_empty() && self.context.is_empty()
+        } else if algo == HashAlgorithm::SipHash24 {
+            self.key.is_empty()
+        } else if algo.max_seed().is_some() {
+            self.seed == 0
+        } else {
+            true
+        }
+    }
+
+    /// 检查参数长度是否符合算法要求
+    pub fn validate(&self, algo: HashAlgorithm) -> Result<(), String> {
+        if let Some(max) = algo.max_output_len() {
+            if self.output_len == 0 || self.output_len > max {
+                return Err(format!("{} 输出长度应为 1-{} 字节", algo.name(), max));
+            }
+        }
+        if algo.is_blake2() {
+            let max_key = algo.max_output_len().unwrap_or(32);
+            let max_param = algo.blake2_param_len();
+            if self.key.len() > max_key {
+                return Err(format!("{} 密钥最长 {} 字节", algo.name(), max_key));
+            }
+            if self.salt.len() > max_param || self.personalization.len() > max_param {
+                return Err(format!("{} salt / personalization 最长 {} 字节", algo.name(), max_param));
+            }
+        }
+        if algo.is_blake3() {
+            if !self.key.is_empty() && !self.context.is_empty() {
+                return Err("BLAKE3 密钥与 derive_key context 只能二选一".to_string());
+            }
+            if !self.key.is_empty() && self.key.len() != 32 {
+                return Err(format!("BLAKE3 密钥必须为 32 字节，当前为 {} 字节", self.key.len()));
+            }
+        }
+        if let Some(max) = algo.max_seed() {
+            if self.seed > max {
+                return Err(format!("{} 的种子最大为 {}", algo.name(), max));
+            }
+        }
+        if algo == HashAlgorithm::SipHash24 && !self.key.is_empty() && self.key.len() != 16 {
+            return Err(format!("SipHash 密钥必须为 16 字节 (留空为全零密钥)，当前为 {} 字节", self.key.len()));
+        }
+        Ok(())
+    }
+
+    /// 公式预览中的参数标记，如 `[32]`、`[64,"email"]` 或 `[key="k"]`；标准用法为空
+    pub fn tag(&self, algo: HashAlgorithm) -> String {
+        let mut parts = Vec::new();
+        if algo.is_xof() || (algo.is_blake2() && Some(self.output_len) != algo.max_output_len()) {
+            parts.push(self.output_len.to_string());
+        }
+        if algo.has_customization() && !self.customization.is_empty() {
+            parts.push(format!("{:?}", self.customization));
+        }
+        if algo.max_seed().is_some() && self.seed != 0 {
+            parts.push(format!("seed={}", self.seed));
+        }
+        let mut named = Vec::new();
+        if algo.is_blake2() || algo.is_blake3() || algo == HashAlgorithm::SipHash24 {
+            named.push(("key", &self.key));
+        }
+        if algo.is_blake2() {
+            named.push(("salt", &self.salt));
+            named.push(("person", &self.personalization));
+        }
+        if algo.is_blake3() {
+            named.push(("ctx", &self.context));
+        }
+        for (name, value) in named {
+            if !value.is_empty() {
+                parts.push(format!("{}={:?}", name, value));
+            }
+        }
+        if parts.is_empty() {
+            String::new()
+        } else {
+            format!("[{}]", parts.join(","))
+        }
+    }
+
+    fn blake3_hasher(&self) -> blake3::Hasher {
+        if !self.context.is_empty() {
+            blake3::Hasher::new_derive_key(&self.context)
+        } else if let Ok(key) = <[u8; 32]>::try_from(self.key.as_bytes()) {
+            blake3::Hasher::new_keyed(&key)
+        } else {
+            blake3::Hasher::new()
+        }
+    }
+
+    fn siphash_key(&self) -> [u8; 16] {
+        <[u8; 16]>::try_from(self.key.as_bytes()).unwrap_or_default()
+    }
+}
+
+/// FNV-1 (`alternate` 为 false) / FNV-1a 32 位
+fn fnv32(bytes: &[u8], alternate: bool) -> u32 {
+    bytes.iter().fold(0x811c9dc5u32, |hash, &b| {
+        if alternate {
+            (hash ^ b as u32).wrapping_mul(0x01000193)
+        } else {
+            hash.wrapping_mul(0x01000193) ^ b as u32
+        }
+    })
+}
+
+/// FNV-1 (`alternate` 为 false) / FNV-1a 64 位
+fn fnv64(bytes: &[u8], alternate: bool) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325u64, |hash, &b| {
+        if alternate {
+            (hash ^ b as u64).wrapping_mul(0x100000001b3)
+        } else {
+            hash.wrapping_mul(0x100000001b3) ^ b as u64
+        }
+    })
+}
+
+fn xof_read(mut hasher: impl ExtendableOutput, bytes: &[u8], len: usize) -> Vec<u8> {
+    let mut out = vec![0u8; len];
+    digest::Update::update(&mut hasher, bytes);
+    hasher.finalize_xof_into(&mut out);
+    out
+}
+
+/// 计算原始摘要字节。`HashAlgorithm::None` 原样返回输入，算法用不到的参数被忽略。
+/// 参数不合法 (见 `HashParams::validate`) 时返回错误说明。
+pub fn digest(algo: HashAlgorithm, params: &HashParams, bytes: &[u8]) -> Result<Vec<u8>, String> {
+    params.validate(algo)?;
+    Ok(digest_unchecked(algo, params, bytes))
+}
+
+/// 以标准参数 (`HashParams::for_algo`，总是合法) 计算摘要
+pub fn standard_digest(algo: HashAlgorithm, bytes: &[u8]) -> Vec<u8> {
+    digest_unchecked(algo, &HashParams::for_algo(algo), bytes)
+}
+
+fn digest_unchecked(algo: HashAlgorithm, params: &HashParams, bytes: &[u8]) -> Vec<u8> {
+    let custom = params.customization.as_bytes();
+    match algo {
+        HashAlgorithm::None => bytes.to_vec(),
+        HashAlgorithm::Md5 => Md5::digest(bytes).to_vec(),
+        HashAlgorithm::Sha1 => Sha1::digest(bytes).to_vec(),
+        HashAlgorithm::Sha224 => Sha224::digest(bytes).to_vec(),
+        HashAlgorithm::Sha256 => Sha256::digest(bytes).to_vec(),
+        HashAlgorithm::Sha384 => Sha384::digest(bytes).to_vec(),
+        HashAlgorithm::Sha512 => Sha512::digest(bytes).to_vec(),
+        HashAlgorithm::Sha512_224 => Sha512_224::digest(bytes).to_vec(),
+        HashAlgorithm::Sha512_256 => Sha512_256::digest(bytes).to_vec(),
+        HashAlgorithm::Sha3_224 => Sha3_224::digest(bytes).to_vec(),
+        HashAlgorithm::Sha3_256 => Sha3_256::digest(bytes).to_vec(),
+        HashAlgorithm::Sha3_384 => Sha3_384::digest(bytes).to_vec(),
+        HashAlgorithm::Sha3_512 => Sha3_512::digest(bytes).to_vec(),
+        HashAlgorithm::Keccak256 => Keccak256::digest(bytes).to_vec(),
+        HashAlgorithm::Keccak512 => Keccak512::digest(bytes).to_vec(),
+        HashAlgorithm::Sm3 => Sm3::digest(bytes).to_vec(),
+        HashAlgorithm::Streebog256 => Streebog256::digest(bytes).to_vec(),
+        HashAlgorithm::Streebog512 => Streebog512::digest(bytes).to_vec(),
+        HashAlgorithm::Gost94 => Gost94Test::digest(bytes).to_vec(),
+        HashAlgorithm::Gost94CryptoPro => Gost94CryptoPro::digest(bytes).to_vec(),
+        HashAlgorithm::Ripemd128 => Ripemd128::digest(bytes).to_vec(),
+        HashAlgorithm::Ripemd160 => Ripemd160::digest(bytes).to_vec(),
+        HashAlgorithm::Ripemd256 => Ripemd256::digest(bytes).to_vec(),
+        HashAlgorithm::Ripemd320 => Ripemd320::digest(bytes).to_vec(),
+        HashAlgorithm::Whirlpool => Whirlpool::digest(bytes).to_vec(),
+        HashAlgorithm::Tiger => Tiger::digest(bytes).to_vec(),
+        HashAlgorithm::Md2 => Md2::digest(bytes).to_vec(),
+        HashAlgorithm::Blake2b => blake2b_simd::Params::new()
+            .hash_length(params.output_len)
+            .key(params.key.as_bytes())
+            .salt(params.salt.as_bytes())
+            .personal(params.personalization.as_bytes())
+            .hash(bytes)
+            .as_bytes()
+            .to_vec(),
+        HashAlgorithm::Blake2s => blake2s_simd::Params::new()
+            .hash_length(params.output_len)
+            .key(params.key.as_bytes())
+            .salt(params.salt.as_bytes())
+            .personal(params.personalization.as_bytes())
+            .hash(bytes)
+            .as_bytes()
+            .to_vec(),
+        HashAlgorithm::Blake3 => params.blake3_hasher().update(bytes).finalize().as_bytes().to_vec(),
+        HashAlgorithm::Shake128 => xof_read(Shake128::default(), bytes, params.output_len),
+        HashAlgorithm::Shake256 => xof_read(Shake256::default(), bytes, params.output_len),
+        HashAlgorithm::CShake128 => xof_read(CShake128::from_core(CShake128Core::new(custom)), bytes, params.output_len),
+        HashAlgorithm::CShake256 => xof_read(CShake256::from_core(CShake256Core::new(custom)), bytes, params.output_len),
+        HashAlgorithm::KangarooTwelve => {
+            xof_read(KangarooTwelve::from_core(KangarooTwelveCore::new(custom)), bytes, params.output_len)
+        }
+        HashAlgorithm::Blake3Xof => {
+            let mut out = vec![0u8; params.output_len];
+            params.blake3_hasher().update(bytes).finalize_xof().fill(&mut out);
+            out
+        }
+        HashAlgorithm::Crc32 => Crc::<u32>::new(&CRC_32_ISO_HDLC).checksum(bytes).to_be_bytes().to_vec(),
+        HashAlgorithm::Crc32c => Crc::<u32>::new(&CRC_32_ISCSI).checksum(bytes).to_be_bytes().to_vec(),
+        HashAlgorithm::Crc64 => Crc::<u64>::new(&CRC_64_XZ).checksum(bytes).to_be_bytes().to_vec(),
+        HashAlgorithm::Adler32 => adler2::adler32_slice(bytes).to_be_bytes().to_vec(),
+        HashAlgorithm::XxHash32 => xxh32(bytes, params.seed as u32).to_be_bytes().to_vec(),
+        HashAlgorithm::XxHash64 => xxh64(bytes, params.seed).to_be_bytes().to_vec(),
+        HashAlgorithm::Xxh3_64 => xxh3_64_with_seed(bytes, params.seed).to_be_bytes().to_vec(),
+        HashAlgorithm::Murmur3_32 => {
+            // 从内存切片读取不会出错
+            murmur3::murmur3_32(&mut &bytes[..], params.seed as u32).unwrap_or_default().to_be_bytes().to_vec()
+        }
+        HashAlgorithm::Fnv132 => fnv32(bytes, false).to_be_bytes().to_vec(),
+        HashAlgorithm::Fnv1a32 => fnv32(bytes, true).to_be_bytes().to_vec(),
+        HashAlgorithm::Fnv164 => fnv64(bytes, false).to_be_bytes().to_vec(),
+        HashAlgorithm::Fnv1a64 => fnv64(bytes, true).to_be_bytes().to_vec(),
+        HashAlgorithm::SipHash24 => SipHasher24::new_with_key(&params.siphash_key()).hash(bytes).to_be_bytes().to_vec(),
+    }
+}
+
+/// 嵌套计算时内层结果传给外层的编码方式。
+/// 默认小写 hex (大多数 PHP 写法)，Raw 对应 MySQL5 / Java / .NET 常见的原始字节链式调用。
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum SaltMode {
-    None,
-    Prefix,
-    Suffix,
-    Both,
-    Custom,
+pub enum OutputEncoding {
+    Raw,
+    LowerHex,
+    UpperHex,
+    Base64,
 }
 
-impl SaltMode {
-    pub fn all() -> &'static [SaltMode] {
+impl OutputEncoding {
+    pub fn all() -> &'static [OutputEncoding] {
         &[
-            SaltMode::None,
-            SaltMode::Prefix,
-            SaltMode::Suffix,
-            SaltMode::Both,
-            SaltMode::Custom,
+            OutputEncoding::Raw,
+            OutputEncoding::LowerHex,
+            OutputEncoding::UpperHex,
+            OutputEncoding::Base64,
         ]
     }
 
     pub fn name(&self) -> &'static str {
         match self {
-            SaltMode::None => "不加盐",
-            SaltMode::Prefix => "前缀 (Salt + Data)",
-            SaltMode::Suffix => "后缀 (Data + Salt)",
-            SaltMode::Both => "前后缀 (Salt + Data + Salt)",
-            SaltMode::Custom => "自定义 (积木模式)",
-        }
-    }
-}
-
-pub fn calculate_hash(
-    algo: HashAlgorithm,
-    input: &str,
-    salt: &str,
-    mode: SaltMode,
-    custom_data: Option<String>,
-) -> String {
-    let data = match mode {
-        SaltMode::None => input.to_string(),
-        SaltMode::Prefix => format!("{}{}", salt, input),
-        SaltMode::Suffix => format!("{}{}", input, salt),
-        SaltMode::Both => format!("{}{}{}", salt, input, salt),
-        SaltMode::Custom => custom_data.unwrap_or_default(),
-    };
+            OutputEncoding::Raw => "原始字节 (raw)",
+            OutputEncoding::LowerHex => "hex (小写)",
+            OutputEncoding::UpperHex => "HEX (大写)",
+            OutputEncoding::Base64 => "Base64",
+        }
+    }
 
-    let bytes = data.as_bytes();
+    /// 公式预览中使用的短标记
+    pub fn tag(&self) -> &'static str {
+        match self {
+            OutputEncoding::Raw => "raw",
+            OutputEncoding::LowerHex => "hex",
+            OutputEncoding::UpperHex => "HEX",
+            OutputEncoding::Base64 => "b64",
+        }
+    }
 
-    match algo {
-        HashAlgorithm::None => data,
-        HashAlgorithm::Md5 => {
-            let mut hasher = Md5::new();
-            hasher.update(bytes);
-            hex::encode(hasher.finalize())
-        }
-        HashAlgorithm::Sha1 => {
-            let mut hasher = Sha1::new();
-            hasher.update(bytes);
-            hex::encode(hasher.finalize())
-        }
-        HashAlgorithm::Sha224 => {
-            let mut hasher = Sha224::new();
-            hasher.update(bytes);
-            hex::encode(hasher.finalize())
-        }
-        HashAlgorithm::Sha256 => {
-            let mut hasher = Sha256::new();
-            hasher.update(bytes);
-            hex::encode(hasher.finalize())
-        }
-        HashAlgorithm::Sha384 => {
-            let mut hasher = Sha384::new();
-            hasher.update(bytes);
-            hex::encode(hasher.finalize())
-        }
-        HashAlgorithm::Sha512 => {
-            let mut hasher = Sha512::new();
-            hasher.update(bytes);
-            hex::encode(hasher.finalize())
-        }
-        HashAlgorithm::Sha3_224 => {
-            let mut hasher = Sha3_224::new();
-            hasher.update(bytes);
-            hex::encode(hasher.finalize())
-        }
-        HashAlgorithm::Sha3_256 => {
-            let mut hasher = Sha3_256::new();
-            hasher.update(bytes);
-            hex::encode(hasher.finalize())
-        }
-        HashAlgorithm::Sha3_384 => {
-            let mut hasher = Sha3_384::new();
-            hasher.update(bytes);
-            hex::encode(hasher.finalize())
-        }
-        HashAlgorithm::Sha3_512 => {
-            let mut hasher = Sha3_512::new();
-            hasher.update(bytes);
-            hex::encode(hasher.finalize())
-        }
-        HashAlgorithm::Sm3 => {
-            let mut hasher = Sm3::new();
-            hasher.update(bytes);
-            hex::encode(hasher.finalize())
-        }
-        HashAlgorithm::Ripemd160 => {
-            let mut hasher = Ripemd160::new();
-            hasher.update(bytes);
-            hex::encode(hasher.finalize())
-        }
-        HashAlgorithm::Whirlpool => {
-            let mut hasher = Whirlpool::new();
-            hasher.update(bytes);
-            hex::encode(hasher.finalize())
-        }
-        HashAlgorithm::Blake2b => {
-            let mut hasher = Blake2b512::new();
-            hasher.update(bytes);
-            hex::encode(hasher.finalize())
-        }
-        HashAlgorithm::Blake2s => {
-            let mut hasher = Blake2s256::new();
-            hasher.update(bytes);
-            hex::encode(hasher.finalize())
-        }
-        HashAlgorithm::Blake3 => {
-            let mut hasher = blake3::Hasher::new();
-            hasher.update(bytes);
-            hex::encode(hasher.finalize().as_bytes())
-        }
-    }
-}
-
-pub fn calculate_complex_hashes(pass: &str, salt: &str) -> Vec<(String, String)> {
+    pub fn encode(&self, bytes: &[u8]) -> Vec<u8> {
+        match self {
+            OutputEncoding::Raw => bytes.to_vec(),
+            OutputEncoding::LowerHex => hex::encode(bytes).into_bytes(),
+            OutputEncoding::UpperHex => hex::encode_upper(bytes).into_bytes(),
+            OutputEncoding::Base64 => BASE64_STANDARD.encode(bytes).into_bytes(),
+        }
+    }
+}
+
+/// 口令 / 盐值输入框内容转换为字节的方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputEncoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Gbk,
+    Gb18030,
+    Big5,
+    Latin1,
+    Hex,
+    Base64,
+}
+
+impl InputEncoding {
+    pub fn all() -> &'static [InputEncoding] {
+        &[
+            InputEncoding::Utf8,
+            InputEncoding::Utf16Le,
+            InputEncoding::Utf16Be,
+            InputEncoding::Gbk,
+            InputEncoding::Gb18030,
+            InputEncoding::Big5,
+            InputEncoding::Latin1,
+            InputEncoding::Hex,
+            InputEncoding::Base64,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            InputEncoding::Utf8 => "UTF-8",
+            InputEncoding::Utf16Le => "UTF-16LE",
+            InputEncoding::Utf16Be => "UTF-16BE",
+            InputEncoding::Gbk => "GBK",
+            InputEncoding::Gb18030 => "GB18030",
+            InputEncoding::Big5 => "Big5",
+            InputEncoding::Latin1 => "Latin-1",
+            InputEncoding::Hex => "hex 字节",
+            InputEncoding::Base64 => "Base64 字节",
+        }
+    }
+
+    /// 算法推算时依次尝试的字符集：目标系统常按 GBK / UTF-16 等而非 UTF-8 计算中文口令
+    pub fn charset_variants() -> &'static [InputEncoding] {
+        &[
+            InputEncoding::Utf8,
+            InputEncoding::Gbk,
+            InputEncoding::Utf16Le,
+            InputEncoding::Big5,
+            InputEncoding::Latin1,
+        ]
+    }
+
+    /// 文本编码 (hex / Base64 直接描述字节，不是文本)
+    pub fn is_text(&self) -> bool {
+        !matches!(self, InputEncoding::Hex | InputEncoding::Base64)
+    }
+
+    /// 按文本编码转换，无法表示的字符报错而不是静默替换
+    pub fn encode_text(&self, text: &str) -> Result<Vec<u8>, String> {
+        let multibyte = |encoding: &'static encoding_rs::Encoding| {
+            let (bytes, _, had_errors) = encoding.encode(text);
+            if had_errors {
+                Err(format!("存在 {} 无法表示的字符", self.name()))
+            } else {
+                Ok(bytes.into_owned())
+            }
+        };
+        match self {
+            InputEncoding::Utf8 => Ok(text.as_bytes().to_vec()),
+            InputEncoding::Utf16Le => Ok(text.encode_utf16().flat_map(|u| u.to_le_bytes()).collect()),
+            InputEncoding::Utf16Be => Ok(text.encode_utf16().flat_map(|u| u.to_be_bytes()).collect()),
+            InputEncoding::Gbk => multibyte(encoding_rs::GBK),
+            InputEncoding::Gb18030 => multibyte(encoding_rs::GB18030),
+            InputEncoding::Big5 => multibyte(encoding_rs::BIG5),
+            InputEncoding::Latin1 => text
+                .chars()
+                .map(|c| u8::try_from(u32::from(c)).map_err(|_| format!("字符 '{}' 超出 Latin-1 范围", c)))
+                .collect(),
+            InputEncoding::Hex => {
+                let digits: String = text.split_whitespace().collect();
+                let digits = digits.strip_prefix("0x").unwrap_or(&digits);
+                hex::decode(digits).map_err(|e| format!("hex 解析失败: {}", e))
+            }
+            InputEncoding::Base64 => {
+                let trimmed = text.trim();
+                BASE64_STANDARD
+                    .decode(trimmed)
+                    .or_else(|_| BASE64_STANDARD_NO_PAD.decode(trimmed))
+                    .or_else(|_| BASE64_URL_SAFE.decode(trimmed))
+                    .or_else(|_| BASE64_URL_SAFE_NO_PAD.decode(trimmed))
+                    .map_err(|e| format!("Base64 解析失败: {}", e))
+            }
+        }
+    }
+}
+
+/// 输入框的解释方式：编码 + 是否解析转义序列
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputFormat {
+    pub encoding: InputEncoding,
+    pub escapes: bool,
+}
+
+impl Default for InputFormat {
+    fn default() -> Self {
+        Self { encoding: InputEncoding::Utf8, escapes: false }
+    }
+}
+
+impl InputFormat {
+    /// 导出元数据中的编码说明
+    pub fn describe(&self) -> String {
+        if self.escapes && self.encoding.is_text() {
+            format!("{}+转义", self.encoding.name())
+        } else {
+            self.encoding.name().to_string()
+        }
+    }
+
+    /// 转换为参与计算的字节。
+    /// 开启转义时 `\n` `\r` `\t` `\0` `\\` `\u{..}` 按字符编码，`\xNN` 为原样插入的单个字节；
+    /// hex / Base64 输入不做转义解析。
+    pub fn bytes(&self, text: &str) -> Result<Vec<u8>, String> {
+        if !self.escapes || !self.encoding.is_text() {
+            return self.encoding.encode_text(text);
+        }
+        let mut out = Vec::new();
+        for segment in unescape(text)? {
+            match segment {
+                Segment::Text(text) => out.extend(self.encoding.encode_text(&text)?),
+                Segment::Byte(byte) => out.push(byte),
+            }
+        }
+        Ok(out)
+    }
+
+    /// 解析转义后的 Unicode 文本，供按 UTF-16 计算的算法 (md5(unicode)、NTLM、LM) 使用。
+    /// hex / Base64 输入或含 `\xNN` 原始字节时没有对应文本，返回 None
+    pub fn text(&self, text: &str) -> Option<String> {
+        if !self.encoding.is_text() {
+            return None;
+        }
+        if !self.escapes {
+            return Some(text.to_string());
+        }
+        unescape(text)
+            .ok()?
+            .into_iter()
+            .map(|segment| match segment {
+                Segment::Text(text) => Some(text),
+                Segment::Byte(_) => None,
+            })
+            .collect()
+    }
+}
+
+/// 转义解析的结果片段：按字符编码的文本，或 `\xNN` 原始字节
+enum Segment {
+    Text(String),
+    Byte(u8),
+}
+
+fn unescape(text: &str) -> Result<Vec<Segment>, String> {
+    let mut out = Vec::new();
+    let mut segment = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            segment.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => segment.push('\n'),
+            Some('r') => segment.push('\r'),
+            Some('t') => segment.push('\t'),
+            Some('0') => segment.push('\0'),
+            Some('\\') => segment.push('\\'),
+            Some('x') => {
+                let digits: String = chars.by_ref().take(2).collect();
+                let byte = u8::from_str_radix(&digits, 16)
+                    .ok()
+                    .filter(|_| digits.len() == 2)
+                    .ok_or_else(|| format!("转义 \\x{} 需要两位十六进制", digits))?;
+                out.push(Segment::Text(std::mem::take(&mut segment)));
+                out.push(Segment::Byte(byte));
+            }
+            Some('u') => {
+                let rest = chars.as_str();
+                let code = rest
+                    .strip_prefix('{')
+                    .and_then(|r| r.split_once('}'))
+                    .and_then(|(digits, _)| u32::from_str_radix(digits, 16).ok().and_then(char::from_u32))
+                    .ok_or("转义 \\u 需写成 \\u{XXXX}")?;
+                let consumed = rest.find('}').map_or(0, |i| i + 1);
+                chars = rest[consumed..].chars();
+                segment.push(code);
+            }
+            Some(other) => return Err(format!("未知转义序列 \\{}", other)),
+            None => return Err("末尾的 \\ 不完整".to_string()),
+        }
+    }
+    out.push(Segment::Text(segment));
+    Ok(out)
+}
+
+/// 结果的显示格式，只影响界面显示、复制与对比，不改变积木之间传递的编码 (见 `OutputEncoding`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisplayFormat {
+    LowerHex,
+    UpperHex,
+    Base64,
+    Base64Url,
+    Base32,
+    ColonHex,
+    CArray,
+}
+
+impl DisplayFormat {
+    pub fn all() -> &'static [DisplayFormat] {
+        &[
+            DisplayFormat::LowerHex,
+            DisplayFormat::UpperHex,
+            DisplayFormat::Base64,
+            DisplayFormat::Base64Url,
+            DisplayFormat::Base32,
+            DisplayFormat::ColonHex,
+            DisplayFormat::CArray,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DisplayFormat::LowerHex => "hex (小写)",
+            DisplayFormat::UpperHex => "HEX (大写)",
+            DisplayFormat::Base64 => "Base64",
+            DisplayFormat::Base64Url => "Base64URL (无填充)",
+            DisplayFormat::Base32 => "Base32",
+            DisplayFormat::ColonHex => "冒号分隔 hex (aa:bb)",
+            DisplayFormat::CArray => "C 字节数组",
+        }
+    }
+
+    /// 结果行上使用的短标记
+    pub fn tag(&self) -> &'static str {
+        match self {
+            DisplayFormat::LowerHex => "hex",
+            DisplayFormat::UpperHex => "HEX",
+            DisplayFormat::Base64 => "b64",
+            DisplayFormat::Base64Url => "b64url",
+            DisplayFormat::Base32 => "b32",
+            DisplayFormat::ColonHex => "a:b",
+            DisplayFormat::CArray => "C",
+        }
+    }
+
+    pub fn format(&self, bytes: &[u8]) -> String {
+        match self {
+            DisplayFormat::LowerHex => hex::encode(bytes),
+            DisplayFormat::UpperHex => hex::encode_upper(bytes),
+            DisplayFormat::Base64 => BASE64_STANDARD.encode(bytes),
+            DisplayFormat::Base64Url => BASE64_URL_SAFE_NO_PAD.encode(bytes),
+            DisplayFormat::Base32 => base32_encode(bytes),
+            DisplayFormat::ColonHex => bytes.iter().map(|b| format!("{:02x}", b)).collect::<Vec<_>>().join(":"),
+            DisplayFormat::CArray => {
+                let items: Vec<String> = bytes.iter().map(|b| format!("0x{:02x}", b)).collect();
+                format!("{{ {} }}", items.join(", "))
+            }
+        }
+    }
+
+    /// 把 hex 形式的批量结果转换为本格式，mysql5 的 `*` 前缀保留在转换结果之前；
+    /// 文本行 (见 `is_text_row`) 即使恰好全是 hex 字符也原样返回
+    pub fn reformat(&self, label: &str, value: &str) -> String {
+        if is_text_row(label) {
+            return value.to_string();
+        }
+        let (prefix, digits) = match value.strip_prefix('*') {
+            Some(rest) => ("*", rest),
+            None => ("", value),
+        };
+        match hex::decode(digits) {
+            Ok(bytes) if !bytes.is_empty() => format!("{}{}", prefix, self.format(&bytes)),
+            _ => value.to_string(),
+        }
+    }
+
+    /// 对比目标与显示值是否一致，Base64 区分大小写，其余格式不区分
+    pub fn matches(&self, shown: &str, target: &str) -> bool {
+        match self {
+            DisplayFormat::Base64 | DisplayFormat::Base64Url => shown == target,
+            _ => shown.eq_ignore_ascii_case(target),
+        }
+    }
+}
+
+/// 对比目标与结果字节的匹配程度
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchKind {
+    Exact,
+    /// 目标是结果的开头部分 (截断的哈希)
+    Prefix,
+    Suffix,
+    /// 目标是结果中间的一段，如 md5 的第 8-24 位
+    Middle,
+}
+
+impl MatchKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            MatchKind::Exact => "完全一致",
+            MatchKind::Prefix => "前缀匹配 (截断)",
+            MatchKind::Suffix => "后缀匹配",
+            MatchKind::Middle => "中间片段匹配",
+        }
+    }
+}
+
+/// 部分匹配要求的最少字节数，避免过短的目标到处命中
+const PARTIAL_MATCH_MIN: usize = 4;
+
+/// 把对比目标按可能的编码解码为字节串：hex (可带 `0x` 前缀、`:`/`-`/空格分隔或 C 数组形式)、
+/// Base64 与 Base64URL (有无填充均可)。同一目标可能有多种解释，全部返回。
+pub fn decode_target(target: &str) -> Vec<Vec<u8>> {
+    let target = target.trim();
+    let mut candidates = Vec::new();
+
+    let inner = target.trim_start_matches('{').trim_end_matches('}');
+    let tokens: Vec<&str> = inner
+        .split(|c: char| c == ',' || c == ':' || c == '-' || c.is_whitespace())
+        .filter(|t| !t.is_empty())
+        .map(|t| t.trim_start_matches("0x").trim_start_matches("0X"))
+        .collect();
+    // 多段时每段必须是一个字节，避免把 "ab cd12" 之类的文本误当作 hex
+    if !tokens.is_empty() && (tokens.len() == 1 || tokens.iter().all(|t| t.len() == 2)) {
+        if let Ok(bytes) = hex::decode(tokens.concat()) {
+            candidates.push(bytes);
+        }
+    }
+
+    let engines = [&BASE64_STANDARD, &BASE64_STANDARD_NO_PAD, &BASE64_URL_SAFE, &BASE64_URL_SAFE_NO_PAD];
+    for engine in engines {
+        if let Ok(bytes) = engine.decode(target) {
+            if !candidates.contains(&bytes) {
+                candidates.push(bytes);
+            }
+        }
+    }
+    candidates.retain(|bytes| !bytes.is_empty());
+    candidates
+}
+
+/// 批量结果中不是 hex 摘要的行 (base64 编码、LDAP 与 apr1 格式串)，按文本原样显示与比较
+pub fn is_text_row(label: &str) -> bool {
+    label == "base64" || label.starts_with("ldap_") || label == "apr1"
+}
+
+/// 批量结果对应的字节：hex 结果直接解码，mysql5 去掉 `*` 前缀；文本行为 None
+pub fn result_bytes(label: &str, value: &str) -> Option<Vec<u8>> {
+    if is_text_row(label) {
+        return None;
+    }
+    hex::decode(value.strip_prefix('*').unwrap_or(value)).ok().filter(|bytes| !bytes.is_empty())
+}
+
+/// 按字节比较结果与解码后的目标，取最好的匹配程度
+pub fn compare_bytes(result: &[u8], targets: &[Vec<u8>]) -> Option<MatchKind> {
+    let mut best = None;
+    for target in targets {
+        let kind = if target.as_slice() == result {
+            MatchKind::Exact
+        } else if target.len() < PARTIAL_MATCH_MIN || target.len() >= result.len() {
+            continue;
+        } else if result.starts_with(target) {
+            MatchKind::Prefix
+        } else if result.ends_with(target) {
+            MatchKind::Suffix
+        } else if result.windows(target.len()).any(|w| w == target.as_slice()) {
+            MatchKind::Middle
+        } else {
+            continue;
+        };
+        if kind == MatchKind::Exact {
+            return Some(kind);
+        }
+        best = best.or(Some(kind));
+    }
+    best
+}
+
+/// RFC 4648 Base32 (带 `=` 填充)
+fn base32_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+    let mut out = String::new();
+    for chunk in bytes.chunks(5) {
+        let mut block = [0u8; 5];
+        block[..chunk.len()].copy_from_slice(chunk);
+        let value = block.iter().fold(0u64, |v, b| v << 8 | *b as u64);
+        // 每 5 字节输出 8 个字符，不足时按有效位数输出并补 `=`
+        let chars = (chunk.len() * 8).div_ceil(5);
+        for i in 0..8 {
+            if i < chars {
+                out.push(ALPHABET[((value >> (35 - 5 * i)) & 0x1f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// 校验和列表中的行：标签与算法
+const CHECKSUM_ROWS: [(&str, HashAlgorithm); 13] = [
+    ("crc32", HashAlgorithm::Crc32),
+    ("crc32c", HashAlgorithm::Crc32c),
+    ("crc64_xz", HashAlgorithm::Crc64),
+    ("adler32", HashAlgorithm::Adler32),
+    ("xxh32", HashAlgorithm::XxHash32),
+    ("xxh64", HashAlgorithm::XxHash64),
+    ("xxh3_64", HashAlgorithm::Xxh3_64),
+    ("murmur3_32", HashAlgorithm::Murmur3_32),
+    ("fnv1_32", HashAlgorithm::Fnv132),
+    ("fnv1a_32", HashAlgorithm::Fnv1a32),
+    ("fnv1_64", HashAlgorithm::Fnv164),
+    ("fnv1a_64", HashAlgorithm::Fnv1a64),
+    ("siphash24", HashAlgorithm::SipHash24),
+];
+
+/// 非加密校验和，`params` 提供 xxHash / MurmurHash3 的种子与 SipHash 密钥
+pub fn calculate_checksums(pass: &[u8], params: &HashParams) -> Vec<(String, String)> {
+    // 参数不合法的行不输出，错误由界面在参数设置处提示 (见 `omitted_checksums`)
+    CHECKSUM_ROWS
+        .iter()
+        .filter_map(|(label, algo)| Some((label.to_string(), hex::encode(digest(*algo, params, pass).ok()?))))
+        .collect()
+}
+
+/// 因参数不合法 (如 32 位种子超出范围、SipHash 密钥长度不对) 而省略的校验和行及原因
+pub fn omitted_checksums(params: &HashParams) -> Vec<(&'static str, String)> {
+    CHECKSUM_ROWS.iter().filter_map(|(label, algo)| Some((*label, params.validate(*algo).err()?))).collect()
+}
+
+/// `pass` / `salt` 为按输入编码转换后的字节；UTF-16 类算法 (md5(unicode)、NTLM、LM) 按 `text` 计算，
+/// 口令没有对应文本 (见 `InputFormat::text`) 时不输出这几行。
+/// `xof` 为批量列表中可变输出长度算法使用的参数
+pub fn calculate_complex_hashes(pass: &[u8], text: Option<&str>, salt: &[u8], xof: &HashParams) -> Vec<(String, String)> {
     let mut results = Vec::new();
 
     // Helper closures
@@ -284,7 +1121,7 @@ pub fn calculate_complex_hashes(pass: &str, salt: &str) -> Vec<(String, String)>
     results.push(("base64".to_string(), BASE64_STANDARD.encode(pass)));
 
     // 2. MD5
-    let md5_pass = md5(pass.as_bytes());
+    let md5_pass = md5(pass);
     results.push(("md5".to_string(), md5_pass.clone()));
 
     // 3. MD5 Middle (8-24)
@@ -300,16 +1137,18 @@ pub fn calculate_complex_hashes(pass: &str, salt: &str) -> Vec<(String, String)>
     results.push(("md5(md5(md5($pass)))".to_string(), md5(md5_md5_pass.as_bytes())));
 
     // 6. md5(unicode) - UTF-16LE
-    let pass_utf16: Vec<u8> = pass.encode_utf16().flat_map(|u| u.to_le_bytes()).collect();
-    results.push(("md5(unicode)".to_string(), md5(&pass_utf16)));
+    let pass_utf16: Option<Vec<u8>> = text.map(|t| t.encode_utf16().flat_map(|u| u.to_le_bytes()).collect());
+    if let Some(utf16) = &pass_utf16 {
+        results.push(("md5(unicode)".to_string(), md5(utf16)));
+    }
 
     // 7. md5(base64)
     results.push(("md5(base64)".to_string(), md5(BASE64_STANDARD.encode(pass).as_bytes())));
 
     // 8. mysql (Old Password - skipped for now, using new password style if needed, but user asked for mysql and mysql5)
     // MySQL 4.1+ (mysql5) is SHA1(SHA1(pass))
-    let sha1_pass = sha1(pass.as_bytes());
-    let sha1_sha1_pass = sha1(sha1_pass.as_bytes()); // Note: MySQL uses binary SHA1(SHA1), but usually tools display hex of hex? No, MySQL is hex(SHA1(unhex(SHA1(pass)))). 
+    let sha1_pass = sha1(pass);
+    // Note: MySQL uses binary SHA1(SHA1), but usually tools display hex of hex? No, MySQL is hex(SHA1(unhex(SHA1(pass)))).
     // Actually MySQL 4.1+ PASSWORD() is "*" + hex(SHA1(SHA1(pass_bytes))).
     // Let's implement standard hex(SHA1(hex(SHA1(pass)))) which is common in these tools, OR the actual MySQL one.
     // Given the list "sha1(sha1($psss))" is separate, "mysql5" likely refers to the specific MySQL format.
@@ -319,16 +1158,21 @@ pub fn calculate_complex_hashes(pass: &str, salt: &str) -> Vec<(String, String)>
     
     // Let's implement "mysql5" as * + UPPER(SHA1(SHA1(pass)))
     let mut h1 = Sha1::new();
-    h1.update(pass.as_bytes());
+    h1.update(pass);
     let h1_bytes = h1.finalize();
     let mut h2 = Sha1::new();
     h2.update(h1_bytes);
     results.push(("mysql5".to_string(), format!("*{}", hex::encode(h2.finalize()).to_uppercase())));
 
     // 9. NTLM (MD4 of UTF-16LE)
-    let mut ntlm_hasher = Md4::new();
-    ntlm_hasher.update(&pass_utf16);
-    results.push(("ntlm".to_string(), hex::encode(ntlm_hasher.finalize())));
+    if let (Some(text), Some(utf16)) = (text, &pass_utf16) {
+        let mut ntlm_hasher = Md4::new();
+        ntlm_hasher.update(utf16);
+        results.push(("ntlm".to_string(), hex::encode(ntlm_hasher.finalize())));
+
+        // 9.1 LM (DES, 大写且截断到 14 字节)
+        results.push(("lm".to_string(), hex::encode(crate::ntlm::lm_hash(text))));
+    }
 
     // 10. SHA1
     results.push(("sha1".to_string(), sha1_pass.clone()));
@@ -344,65 +1188,101 @@ pub fn calculate_complex_hashes(pass: &str, salt: &str) -> Vec<(String, String)>
     results.push(("md5(sha1($pass))".to_string(), md5(sha1_pass.as_bytes())));
 
     // 14. sha256
-    let sha256_pass = sha256(pass.as_bytes());
+    let sha256_pass = sha256(pass);
     results.push(("sha256".to_string(), sha256_pass.clone()));
 
     // 15. sha256(md5($pass))
     results.push(("sha256(md5($pass))".to_string(), sha256(md5_pass.as_bytes())));
 
     // 16. sha384
-    results.push(("sha384".to_string(), sha384(pass.as_bytes())));
+    results.push(("sha384".to_string(), sha384(pass)));
 
     // 17. sha512
-    results.push(("sha512".to_string(), sha512(pass.as_bytes())));
+    results.push(("sha512".to_string(), sha512(pass)));
 
     // 18. SM3 (Commercial Cryptography)
-    let sm3_pass = sm3(pass.as_bytes());
+    let sm3_pass = sm3(pass);
     results.push(("sm3".to_string(), sm3_pass.clone()));
 
     // 19. RIPEMD-160
-    results.push(("ripemd160".to_string(), ripemd160(pass.as_bytes())));
+    results.push(("ripemd160".to_string(), ripemd160(pass)));
 
     // 20. Whirlpool
-    results.push(("whirlpool".to_string(), whirlpool(pass.as_bytes())));
+    results.push(("whirlpool".to_string(), whirlpool(pass)));
 
     // 21. SHA3 Family
-    results.push(("sha3_256".to_string(), sha3_256(pass.as_bytes())));
-    results.push(("sha3_512".to_string(), sha3_512(pass.as_bytes())));
+    results.push(("sha3_256".to_string(), sha3_256(pass)));
+    results.push(("sha3_512".to_string(), sha3_512(pass)));
+    results.push(("keccak256".to_string(), hex::encode(Keccak256::digest(pass))));
+    results.push(("keccak512".to_string(), hex::encode(Keccak512::digest(pass))));
 
     // 22. BLAKE Family
-    results.push(("blake2b".to_string(), blake2b(pass.as_bytes())));
-    results.push(("blake2s".to_string(), blake2s(pass.as_bytes())));
-    results.push(("blake3".to_string(), blake3_fn(pass.as_bytes())));
+    results.push(("blake2b".to_string(), blake2b(pass)));
+    results.push(("blake2s".to_string(), blake2s(pass)));
+    results.push(("blake3".to_string(), blake3_fn(pass)));
+
+    // 23. 其他标准与旧算法 (SHA-512/t、GOST、RIPEMD、Tiger、MD2)
+    let legacy_algos = [
+        ("sha512_224", HashAlgorithm::Sha512_224),
+        ("sha512_256", HashAlgorithm::Sha512_256),
+        ("streebog256", HashAlgorithm::Streebog256),
+        ("streebog512", HashAlgorithm::Streebog512),
+        ("gost", HashAlgorithm::Gost94),
+        ("gost_cryptopro", HashAlgorithm::Gost94CryptoPro),
+        ("ripemd128", HashAlgorithm::Ripemd128),
+        ("ripemd256", HashAlgorithm::Ripemd256),
+        ("ripemd320", HashAlgorithm::Ripemd320),
+        ("tiger", HashAlgorithm::Tiger),
+        ("md2", HashAlgorithm::Md2),
+    ];
+    for (label, algo) in legacy_algos {
+        results.push((label.to_string(), hex::encode(standard_digest(algo, pass))));
+    }
+
+    // 24. XOF Family (SHAKE / cSHAKE / KangarooTwelve / BLAKE3 XOF)
+    let xof_algos = [
+        ("shake128", HashAlgorithm::Shake128),
+        ("shake256", HashAlgorithm::Shake256),
+        ("cshake128", HashAlgorithm::CShake128),
+        ("cshake256", HashAlgorithm::CShake256),
+        ("k12", HashAlgorithm::KangarooTwelve),
+        ("blake3_xof", HashAlgorithm::Blake3Xof),
+    ];
+    // 输出长度等参数不合法的行不输出，错误由界面在参数设置处提示
+    for (label, algo) in xof_algos {
+        if let Ok(bytes) = digest(algo, xof, pass) {
+            results.push((label.to_string(), hex::encode(bytes)));
+        }
+    }
 
     // Salted variations
     // md5(md5($pass).$salt);VB;DZ -> md5(md5(pass) + salt)
-    results.push(("md5(md5($pass).$salt)".to_string(), md5(format!("{}{}", md5_pass, salt).as_bytes())));
+    results.push(("md5(md5($pass).$salt)".to_string(), md5(&[md5_pass.as_bytes(), salt].concat())));
 
     // md5($pass.$salt)
-    results.push(("md5($pass.$salt)".to_string(), md5(format!("{}{}", pass, salt).as_bytes())));
+    results.push(("md5($pass.$salt)".to_string(), md5(&[pass, salt].concat())));
 
     // md5($salt.$pass)
-    results.push(("md5($salt.$pass)".to_string(), md5(format!("{}{}", salt, pass).as_bytes())));
+    results.push(("md5($salt.$pass)".to_string(), md5(&[salt, pass].concat())));
 
     // md5($salt.$pass.$salt)
-    results.push(("md5($salt.$pass.$salt)".to_string(), md5(format!("{}{}{}", salt, pass, salt).as_bytes())));
+    results.push(("md5($salt.$pass.$salt)".to_string(), md5(&[salt, pass, salt].concat())));
 
     // md5($salt.md5($pass))
-    results.push(("md5($salt.md5($pass))".to_string(), md5(format!("{}{}", salt, md5_pass).as_bytes())));
+    results.push(("md5($salt.md5($pass))".to_string(), md5(&[salt, md5_pass.as_bytes()].concat())));
 
     // md5(md5($salt).$pass)
-    let md5_salt = md5(salt.as_bytes());
-    results.push(("md5(md5($salt).$pass)".to_string(), md5(format!("{}{}", md5_salt, pass).as_bytes())));
+    let md5_salt = md5(salt);
+    results.push(("md5(md5($salt).$pass)".to_string(), md5(&[md5_salt.as_bytes(), pass].concat())));
 
     // md5($pass.md5($salt))
-    results.push(("md5($pass.md5($salt))".to_string(), md5(format!("{}{}", pass, md5_salt).as_bytes())));
+    results.push(("md5($pass.md5($salt))".to_string(), md5(&[pass, md5_salt.as_bytes()].concat())));
 
     // md5(md5($salt).md5($pass))
-    results.push(("md5(md5($salt).md5($pass))".to_string(), md5(format!("{}{}", md5_salt, md5_pass).as_bytes())));
+    results.push(("md5(md5($salt).md5($pass))".to_string(), md5(&[md5_salt.as_bytes(), md5_pass.as_bytes()].concat())));
 
     // md5(md5($pass).md5($salt))
-    results.push(("md5(md5($pass).md5($salt))".to_string(), md5(format!("{}{}", md5_pass, md5_salt).as_bytes())));
+    results.push(("md5(md5($pass).md5($salt))".to_string(), md5(&[md5_pass.as_bytes(), md5_salt.as_bytes()].concat())));
 
     // md5(substring(md5($pass),8,16))
     if md5_pass.len() >= 24 {
@@ -411,32 +1291,43 @@ pub fn calculate_complex_hashes(pass: &str, salt: &str) -> Vec<(String, String)>
     }
 
     // sha1($pass.$salt)
-    results.push(("sha1($pass.$salt)".to_string(), sha1(format!("{}{}", pass, salt).as_bytes())));
+    results.push(("sha1($pass.$salt)".to_string(), sha1(&[pass, salt].concat())));
 
     // sha1($salt.$pass)
-    results.push(("sha1($salt.$pass)".to_string(), sha1(format!("{}{}", salt, pass).as_bytes())));
+    results.push(("sha1($salt.$pass)".to_string(), sha1(&[salt, pass].concat())));
 
     // sha256($pass.$salt)
-    results.push(("sha256($pass.$salt)".to_string(), sha256(format!("{}{}", pass, salt).as_bytes())));
+    results.push(("sha256($pass.$salt)".to_string(), sha256(&[pass, salt].concat())));
 
     // sha256($salt.$pass)
-    results.push(("sha256($salt.$pass)".to_string(), sha256(format!("{}{}", salt, pass).as_bytes())));
+    results.push(("sha256($salt.$pass)".to_string(), sha256(&[salt, pass].concat())));
 
     // sha512($pass.$salt)
-    results.push(("sha512($pass.$salt)".to_string(), sha512(format!("{}{}", pass, salt).as_bytes())));
+    results.push(("sha512($pass.$salt)".to_string(), sha512(&[pass, salt].concat())));
 
     // sha512($salt.$pass)
-    results.push(("sha512($salt.$pass)".to_string(), sha512(format!("{}{}", salt, pass).as_bytes())));
+    results.push(("sha512($salt.$pass)".to_string(), sha512(&[salt, pass].concat())));
 
     // sm3($pass.$salt)
-    results.push(("sm3($pass.$salt)".to_string(), sm3(format!("{}{}", pass, salt).as_bytes())));
+    results.push(("sm3($pass.$salt)".to_string(), sm3(&[pass, salt].concat())));
 
     // sm3($salt.$pass)
-    results.push(("sm3($salt.$pass)".to_string(), sm3(format!("{}{}", salt, pass).as_bytes())));
+    results.push(("sm3($salt.$pass)".to_string(), sm3(&[salt, pass].concat())));
 
     // sm3($salt.$pass.$salt)
-    results.push(("sm3($salt.$pass.$salt)".to_string(), sm3(format!("{}{}{}", salt, pass, salt).as_bytes())));
+    results.push(("sm3($salt.$pass.$salt)".to_string(), sm3(&[salt, pass, salt].concat())));
 
+    // LDAP / htpasswd 格式 ($salt 作为盐值，{MD5}/{SHA} 不加盐)
+    let ldap_schemes = [
+        ("ldap_md5", "{MD5}"),
+        ("ldap_sha", "{SHA}"),
+        ("ldap_ssha", "{SSHA}"),
+        ("ldap_ssha256", "{SSHA256}"),
+        ("ldap_ssha512", "{SSHA512}"),
+    ];
+    for (label, scheme) in ldap_schemes {
+        results.push((label.to_string(), crate::formats::ldap_encode(scheme, pass, salt)));
+    }
     // MSSQL2015 (SHA2_512) - Assuming 0x0200 prefix + salt + hash
     // But without binary salt, we can't replicate exact MSSQL binary format.
     // We will just do a placeholder or standard salted SHA512 if that's what the user expects.
@@ -447,3 +1338,157 @@ pub fn calculate_complex_hashes(pass: &str, salt: &str) -> Vec<(String, String)>
     
     results
 }
+
+/// 按轮数迭代、单次计算较慢的格式 (apr1 为 1000 轮 MD5)，不放进 `calculate_complex_hashes`，
+/// 避免推算时逐个盐值爆破卡住界面
+pub fn calculate_slow_hashes(pass: &[u8], salt: &[u8]) -> Vec<(String, String)> {
+    vec![("apr1".to_string(), crate::formats::md5_crypt(pass, salt, "$apr1$"))]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex_digest(algo: HashAlgorithm, input: &str) -> String {
+        hex::encode(standard_digest(algo, input.as_bytes()))
+    }
+
+    /// 各算法规范或参考实现中的已知答案
+    #[test]
+    fn known_answer_vectors() {
+        let vectors = [
+            (HashAlgorithm::Sha512_224, "abc", "4634270f707b6a54daae7530460842e20e37ed265ceee9a43e8924aa"),
+            (HashAlgorithm::Sha512_256, "abc", "53048e2681941ef99b2e29b76b4c7dabe4c2d0c634fc6d46e0e2f13107e7af23"),
+            (HashAlgorithm::Streebog256, "", "3f539a213e97c802cc229d474c6aa32a825a360b2a933a949fd925208d9ce1bb"),
+            (
+                HashAlgorithm::Streebog512,
+                "",
+                "8e945da209aa869f0455928529bcae4679e9873ab707b55315f56ceb98bef0a7362f715528356ee83cda5f2aac4c6ad2ba3a715c1bcd81cb8e9f90bf4c1c1a8a",
+            ),
+            (HashAlgorithm::Gost94, "", "ce85b99cc46752fffee35cab9a7b0278abb4c2d2055cff685af4912c49490f8d"),
+            (HashAlgorithm::Gost94, "abc", "f3134348c44fb1b2a277729e2285ebb5cb5e0f29c975bc753b70497c06a4d51d"),
+            (HashAlgorithm::Gost94CryptoPro, "", "981e5f3ca30c841487830f84fb433e13ac1101569b9c13584ac483234cd656c0"),
+            (HashAlgorithm::Gost94CryptoPro, "abc", "b285056dbf18d7392d7677369524dd14747459ed8143997e163b2986f92fd42c"),
+            (HashAlgorithm::Ripemd128, "", "cdf26213a150dc3ecb610f18f6b38b46"),
+            (HashAlgorithm::Ripemd128, "abc", "c14a12199c66e4ba84636b0f69144c77"),
+            (HashAlgorithm::Ripemd160, "abc", "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc"),
+            (HashAlgorithm::Ripemd256, "", "02ba4c4e5f8ecd1877fc52d64d30e37a2d9774fb1e5d026380ae0168e3c5522d"),
+            (HashAlgorithm::Ripemd256, "abc", "afbd6e228b9d8cbbcef5ca2d03e6dba10ac0bc7dcbe4680e1e42d2e975459b65"),
+            (HashAlgorithm::Ripemd320, "", "22d65d5661536cdc75c1fdf5c6de7b41b9f27325ebc61e8557177d705a0ec880151c3a32a00899b8"),
+            (HashAlgorithm::Ripemd320, "abc", "de4c01b3054f8930a79d09ae738e92301e5a17085beffdc1b8d116713e74f82fa942d64cdbc4682d"),
+            (HashAlgorithm::Tiger, "", "3293ac630c13f0245f92bbb1766e16167a4e58492dde73f3"),
+            (HashAlgorithm::Tiger, "abc", "2aab1484e8c158f2bfb8c5ff41b57a525129131c957b5f93"),
+            (HashAlgorithm::Md2, "", "8350e5a3e24c153df2275c9f80692773"),
+            (HashAlgorithm::Md2, "abc", "da853b0d3f88d99b30283a69e6ded6bb"),
+        ];
+        for (algo, input, expected) in vectors {
+            assert_eq!(hex_digest(algo, input), expected, "{} ({:?})", algo.name(), input);
+        }
+    }
+
+    #[test]
+    fn standard_params_are_valid() {
+        for algo in HashAlgorithm::all() {
+            assert!(HashParams::for_algo(*algo).validate(*algo).is_ok(), "{}", algo.name());
+        }
+    }
+
+    #[test]
+    fn reformat_keeps_mysql5_prefix() {
+        let mysql5 = "*6BB4837EB74329105EE4568DDA7DC67ED2CA2AD9";
+        assert_eq!(DisplayFormat::LowerHex.reformat("mysql5", mysql5), "*6bb4837eb74329105ee4568dda7dc67ed2ca2ad9");
+        assert_eq!(DisplayFormat::Base64.reformat("mysql5", mysql5), "*a7SDfrdDKRBe5FaN2n3GftLKKtk=");
+        assert_eq!(DisplayFormat::Base64.reformat("ldap_sha", "{SHA}abc"), "{SHA}abc");
+        // base64 行的值恰好全是 hex 字符时也不按字节转换
+        assert_eq!(DisplayFormat::UpperHex.reformat("base64", "deadbeef"), "deadbeef");
+        assert_eq!(DisplayFormat::UpperHex.reformat("md5_middle", "deadbeef"), "DEADBEEF");
+    }
+
+    #[test]
+    fn decode_target_formats() {
+        let bytes = vec![0xde, 0xad, 0xbe, 0xef];
+        for target in ["deadbeef", "0xDEADBEEF", "de:ad:be:ef", "de-ad-be-ef", "{0xde, 0xad, 0xbe, 0xef}", "3q2+7w==", "3q2-7w"] {
+            assert!(decode_target(target).contains(&bytes), "{}", target);
+        }
+        // 多段时每段必须是一个字节
+        assert!(decode_target("dead beef12").iter().all(|b| b != &[0xde, 0xad, 0xbe, 0xef, 0x12]));
+        assert!(decode_target("").is_empty());
+    }
+
+    #[test]
+    fn compare_bytes_kinds() {
+        let result: Vec<u8> = (0u8..16).collect();
+        let cmp = |target: &[u8]| compare_bytes(&result, &[target.to_vec()]);
+        assert_eq!(cmp(&result), Some(MatchKind::Exact));
+        assert_eq!(cmp(&result[..8]), Some(MatchKind::Prefix));
+        assert_eq!(cmp(&result[8..]), Some(MatchKind::Suffix));
+        assert_eq!(cmp(&result[4..12]), Some(MatchKind::Middle));
+        // 过短的片段不算部分匹配
+        assert_eq!(cmp(&result[..3]), None);
+        assert_eq!(cmp(&[0xff; 8]), None);
+        // 多个候选时取最好的一种
+        assert_eq!(compare_bytes(&result, &[result[..8].to_vec(), result.clone()]), Some(MatchKind::Exact));
+        assert_eq!(result_bytes("mysql5", "*0A0B"), Some(vec![0x0a, 0x0b]));
+        assert_eq!(result_bytes("ldap_sha", "{SHA}abc"), None);
+        assert_eq!(result_bytes("base64", "deadbeef"), None);
+        assert_eq!(result_bytes("crc32", "deadbeef"), Some(vec![0xde, 0xad, 0xbe, 0xef]));
+    }
+
+    #[test]
+    fn invalid_params_are_reported() {
+        let params = HashParams { output_len: 0, ..HashParams::default() };
+        assert!(digest(HashAlgorithm::Shake128, &params, b"abc").is_err());
+    }
+
+    #[test]
+    fn input_format_escapes() {
+        let utf8 = InputFormat { encoding: InputEncoding::Utf8, escapes: true };
+        assert_eq!(utf8.bytes("a\\n\\x00\\u{4e2d}").unwrap(), b"a\n\x00\xe4\xb8\xad");
+        assert_eq!(utf8.text("a\\t\\u{4e2d}").as_deref(), Some("a\t中"));
+        assert_eq!(utf8.text("a\\xff"), None);
+        assert!(utf8.bytes("\\x0").is_err());
+        assert!(utf8.bytes("\\q").is_err());
+
+        let gbk = InputFormat { encoding: InputEncoding::Gbk, escapes: true };
+        assert_eq!(gbk.bytes("中\\xff").unwrap(), [0xd6, 0xd0, 0xff]);
+
+        let plain = InputFormat { encoding: InputEncoding::Utf8, escapes: false };
+        assert_eq!(plain.bytes("a\\n").unwrap(), b"a\\n");
+        assert_eq!(plain.text("a\\n").as_deref(), Some("a\\n"));
+
+        let hex = InputFormat { encoding: InputEncoding::Hex, escapes: true };
+        assert_eq!(hex.bytes("6869").unwrap(), b"hi");
+        assert_eq!(hex.text("6869"), None);
+    }
+
+    #[test]
+    fn utf16_rows_use_decoded_text() {
+        let row = |rows: &[(String, String)], label: &str| rows.iter().find(|(l, _)| l == label).map(|(_, h)| h.clone());
+        let params = HashParams::default();
+        let rows = calculate_complex_hashes(b"password", Some("password"), b"", &params);
+        assert_eq!(row(&rows, "ntlm").as_deref(), Some("8846f7eaee8fb117ad06bdd830b7586c"));
+        assert_eq!(row(&rows, "lm").as_deref(), Some("e52cac67419a9a224a3b108f3fa6cb6d"));
+
+        // GBK 字节不是合法 UTF-8，NTLM 仍应按原文本计算
+        let gbk = InputEncoding::Gbk.encode_text("密码").unwrap();
+        let from_gbk = calculate_complex_hashes(&gbk, Some("密码"), b"", &params);
+        let from_utf8 = calculate_complex_hashes("密码".as_bytes(), Some("密码"), b"", &params);
+        for label in ["md5(unicode)", "ntlm", "lm"] {
+            assert_eq!(row(&from_gbk, label), row(&from_utf8, label));
+        }
+
+        let raw = calculate_complex_hashes(&[0xff, 0xfe], None, b"", &params);
+        assert!(row(&raw, "ntlm").is_none() && row(&raw, "md5(unicode)").is_none());
+    }
+
+    #[test]
+    fn checksum_seed_ranges() {
+        let params = HashParams { seed: u32::MAX as u64 + 1, ..HashParams::default() };
+        let labels: Vec<String> = calculate_checksums(b"abc", &params).into_iter().map(|(label, _)| label).collect();
+        assert!(labels.iter().any(|l| l == "xxh64") && labels.iter().any(|l| l == "xxh3_64"));
+        let omitted: Vec<&str> = omitted_checksums(&params).into_iter().map(|(label, _)| label).collect();
+        assert_eq!(omitted, ["xxh32", "murmur3_32"]);
+        assert!(omitted_checksums(&HashParams { seed: u64::MAX, ..HashParams::default() }).len() == 2);
+        assert!(omitted_checksums(&HashParams::default()).is_empty());
+    }
+}
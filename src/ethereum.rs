@@ -0,0 +1,69 @@
+use sha3::{Digest, Keccak256};
+
+pub fn keccak256(bytes: &[u8]) -> [u8; 32] {
+    Keccak256::digest(bytes).into()
+}
+
+/// 规范化函数/事件签名：去掉所有空白，如 `transfer(address, uint256)` -> `transfer(address,uint256)`
+pub fn normalize_signature(signature: &str) -> String {
+    signature.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+/// 函数选择器：keccak256(签名) 的前 4 字节
+pub fn function_selector(signature: &str) -> String {
+    let hash = keccak256(normalize_signature(signature).as_bytes());
+    format!("0x{}", hex::encode(&hash[..4]))
+}
+
+/// 事件 topic0：keccak256(签名) 完整 32 字节
+pub fn event_topic(signature: &str) -> String {
+    let hash = keccak256(normalize_signature(signature).as_bytes());
+    format!("0x{}", hex::encode(hash))
+}
+
+/// 地址校验和的检查结果
+pub enum ChecksumStatus {
+    /// 输入为全小写或全大写，不含校验信息
+    NoChecksum,
+    Valid,
+    Invalid,
+}
+
+/// EIP-55 校验和地址。返回 (校验和地址, 输入地址的校验状态)
+pub fn checksum_address(address: &str) -> Result<(String, ChecksumStatus), String> {
+    let address = address.trim();
+    let body = address
+        .strip_prefix("0x")
+        .or_else(|| address.strip_prefix("0X"))
+        .unwrap_or(address);
+    if body.len() != 40 {
+        return Err(format!("地址应为 40 位十六进制字符，当前为 {} 位", body.len()));
+    }
+    if !body.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err("地址包含非十六进制字符".to_string());
+    }
+
+    let lower = body.to_ascii_lowercase();
+    let hash = hex::encode(keccak256(lower.as_bytes()));
+    let checksummed: String = lower
+        .chars()
+        .zip(hash.chars())
+        .map(|(c, h)| {
+            // 哈希对应位 >= 8 的字母大写
+            if c.is_ascii_alphabetic() && h.to_digit(16).unwrap_or(0) >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect();
+
+    let status = if body == lower || body == body.to_ascii_uppercase() {
+        ChecksumStatus::NoChecksum
+    } else if body == checksummed {
+        ChecksumStatus::Valid
+    } else {
+        ChecksumStatus::Invalid
+    };
+    Ok((format!("0x{}", checksummed), status))
+}
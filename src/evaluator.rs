@@ -0,0 +1,113 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+
+use crate::blocks::{self, CustomBlock, TraceNode};
+use crate::codegen;
+
+/// 一次积木计算请求
+struct EvalRequest {
+    id: u64,
+    blocks: Vec<CustomBlock>,
+    pass: Vec<u8>,
+    salt: Vec<u8>,
+    /// 积木有变化时才需要重新自检 (自检使用固定样例，与输入无关)
+    check: bool,
+}
+
+/// 积木计算结果
+pub struct EvalOutcome {
+    id: u64,
+    /// 积木参数不合法时为错误说明
+    pub output: Result<Vec<u8>, String>,
+    pub trace: Vec<TraceNode>,
+    pub check: Option<Result<usize, String>>,
+}
+
+/// 自定义积木的后台计算线程。大轮数循环块可能耗时数秒，放在界面线程会卡住输入；
+/// 新请求到达时取消正在进行的计算，积压的请求只计算最新一条
+pub struct Evaluator {
+    tx: Sender<EvalRequest>,
+    rx: Receiver<EvalOutcome>,
+    cancel: Arc<AtomicBool>,
+    /// 最近一次提交的请求号
+    latest: u64,
+    /// 最近一次收到结果的请求号
+    done: u64,
+    pending_check: Option<Result<usize, String>>,
+}
+
+impl Default for Evaluator {
+    fn default() -> Self {
+        let (req_tx, req_rx) = mpsc::channel::<EvalRequest>();
+        let (out_tx, out_rx) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let flag = cancel.clone();
+        thread::spawn(move || {
+            // 被跳过或取消的请求若要求自检，留给之后的请求完成
+            let mut check = false;
+            // 发送端 (界面) 关闭后退出
+            while let Ok(mut request) = req_rx.recv() {
+                check |= request.check;
+                for newer in req_rx.try_iter() {
+                    check |= newer.check;
+                    request = newer;
+                }
+                flag.store(false, Ordering::Relaxed);
+                let traced = blocks::trace(&request.blocks, &request.pass, &request.salt, &flag);
+                if flag.load(Ordering::Relaxed) {
+                    continue;
+                }
+                let (output, trace) = match traced {
+                    Ok(trace) => (Ok(trace.iter().flat_map(|node| node.output.iter().copied()).collect()), trace),
+                    Err(e) => (Err(e), Vec::new()),
+                };
+                let check = std::mem::take(&mut check)
+                    .then(|| codegen::self_check(&request.blocks, &codegen::Program::lower(&request.blocks)));
+                if out_tx.send(EvalOutcome { id: request.id, output, trace, check }).is_err() {
+                    break;
+                }
+            }
+        });
+        Self { tx: req_tx, rx: out_rx, cancel, latest: 0, done: 0, pending_check: None }
+    }
+}
+
+impl Evaluator {
+    pub fn submit(&mut self, blocks: Vec<CustomBlock>, pass: Vec<u8>, salt: Vec<u8>, check: bool) {
+        self.latest += 1;
+        self.cancel.store(true, Ordering::Relaxed);
+        let _ = self.tx.send(EvalRequest { id: self.latest, blocks, pass, salt, check });
+    }
+
+    /// 放弃尚未完成的请求 (如积木参数不合法时)，之后不再返回它们的结果
+    pub fn cancel(&mut self) {
+        self.latest += 1;
+        self.done = self.latest;
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    /// 取出最新请求的结果 (不阻塞)，过期请求的结果丢弃。
+    /// 自检与输入无关，过期结果中的自检仍然有效，随下一次结果一并返回
+    pub fn poll(&mut self) -> Option<EvalOutcome> {
+        let mut current = None;
+        for mut outcome in self.rx.try_iter() {
+            if let Some(check) = outcome.check.take() {
+                self.pending_check = Some(check);
+            }
+            if outcome.id == self.latest {
+                current = Some(outcome);
+            }
+        }
+        let mut outcome = current?;
+        self.done = outcome.id;
+        outcome.check = self.pending_check.take();
+        Some(outcome)
+    }
+
+    /// 最新请求是否仍在计算
+    pub fn is_busy(&self) -> bool {
+        self.done != self.latest
+    }
+}
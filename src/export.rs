@@ -0,0 +1,279 @@
+use std::fmt::Write;
+
+/// 导出文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    /// hashcat / John 通用的 `hash` 或 `hash:salt` 行
+    HashList,
+    /// hashcat 风格 potfile：`hash[:salt]:plain`
+    Potfile,
+}
+
+impl ExportFormat {
+    pub fn all() -> &'static [ExportFormat] {
+        &[ExportFormat::Csv, ExportFormat::Json, ExportFormat::HashList, ExportFormat::Potfile]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "CSV",
+            ExportFormat::Json => "JSON",
+            ExportFormat::HashList => "hashcat / John (hash:salt)",
+            ExportFormat::Potfile => "potfile (hash:plain)",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::HashList => "hash",
+            ExportFormat::Potfile => "pot",
+        }
+    }
+}
+
+/// 一条导出记录：算法、hashcat 模式号、哈希、盐值、明文与输入编码
+#[derive(Clone)]
+pub struct ExportRecord {
+    pub algorithm: String,
+    pub hashcat_mode: Option<u32>,
+    pub hash: String,
+    pub salt: Option<String>,
+    /// 实际参与计算的口令字节
+    pub plain: Option<Vec<u8>>,
+    pub encoding: String,
+}
+
+impl ExportRecord {
+    /// 由批量结果的一行构造；只有公式中含 $salt 的行记录盐值
+    pub fn from_label(label: &str, hash: &str, salt: &str, plain: &[u8], encoding: &str) -> Self {
+        Self {
+            algorithm: label.to_string(),
+            hashcat_mode: hashcat_mode(label),
+            hash: hash.to_string(),
+            salt: label.contains("$salt").then(|| salt.to_string()),
+            plain: Some(plain.to_vec()),
+            encoding: encoding.to_string(),
+        }
+    }
+
+    /// hashcat 文件中的哈希字段 (mysql5 去掉前导 `*`)
+    fn hashcat_hash(&self) -> &str {
+        self.hash.trim_start_matches('*')
+    }
+}
+
+/// 内置批量结果标签对应的 hashcat 模式号。单一算法的标签取 `HashAlgorithm::hashcat_mode`，
+/// 这里只列出组合配方与特定格式
+pub fn hashcat_mode(label: &str) -> Option<u32> {
+    if let Some(algo) = crate::blocks::recipe_algo(label) {
+        return algo.hashcat_mode();
+    }
+    let mode = match label {
+        "md5($pass.$salt)" => 10,
+        "md5($salt.$pass)" => 20,
+        "md5(unicode)" => 70,
+        "ldap_sha" => 101,
+        "sha1($pass.$salt)" => 110,
+        "ldap_ssha" => 111,
+        "sha1($salt.$pass)" => 120,
+        "mysql5" => 300,
+        "ntlm" => 1000,
+        "sha256($pass.$salt)" => 1410,
+        "ldap_ssha256" => 1411,
+        "sha256($salt.$pass)" => 1420,
+        "apr1" => 1600,
+        "sha512($pass.$salt)" => 1710,
+        "ldap_ssha512" => 1711,
+        "sha512($salt.$pass)" => 1720,
+        "md5(md5($pass))" => 2600,
+        "md5(md5($pass).$salt)" => 2611,
+        "md5(md5($salt).md5($pass))" => 2811,
+        "lm" => 3000,
+        "md5(md5(md5($pass)))" => 3500,
+        "md5(md5($salt).$pass)" => 3610,
+        "md5($salt.md5($pass))" => 3710,
+        "md5($pass.md5($salt))" => 3720,
+        "md5($salt.$pass.$salt)" => 3800,
+        "md5(md5($pass).md5($salt))" => 3910,
+        "md5(sha1($pass))" => 4400,
+        "sha1(sha1($pass))" => 4500,
+        "sha1(md5($pass))" => 4700,
+        "sha256(md5($pass))" => 20800,
+        _ => return None,
+    };
+    Some(mode)
+}
+
+/// potfile 中的明文：含 `:` 或非可打印 ASCII 时按 hashcat 惯例写成 `$HEX[...]`
+pub fn potfile_plain(plain: &[u8]) -> String {
+    if plain.iter().all(|b| (0x20..0x7f).contains(b) && *b != b':') && !plain.starts_with(b"$HEX[") {
+        String::from_utf8_lossy(plain).into_owned()
+    } else {
+        format!("$HEX[{}]", hex::encode(plain))
+    }
+}
+
+/// CSV / JSON 中的明文：合法 UTF-8 原样写出，否则 (如 GBK、UTF-16 字节) 写成 `$HEX[...]`
+fn plain_field(plain: &[u8]) -> String {
+    match std::str::from_utf8(plain) {
+        Ok(text) if !text.starts_with("$HEX[") => text.to_string(),
+        _ => format!("$HEX[{}]", hex::encode(plain)),
+    }
+}
+
+pub fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn json_option(value: Option<&str>) -> String {
+    value.map_or_else(|| "null".to_string(), json_string)
+}
+
+/// 按格式生成导出文本。哈希列表与 potfile 只包含行本身，模式号等元数据请用 CSV / JSON
+pub fn render(format: ExportFormat, records: &[ExportRecord]) -> String {
+    let mut out = String::new();
+    match format {
+        ExportFormat::Csv => {
+            out.push_str("algorithm,hashcat_mode,hash,salt,plain,encoding\n");
+            for r in records {
+                let _ = writeln!(
+                    out,
+                    "{},{},{},{},{},{}",
+                    csv_field(&r.algorithm),
+                    r.hashcat_mode.map(|m| m.to_string()).unwrap_or_default(),
+                    csv_field(&r.hash),
+                    csv_field(r.salt.as_deref().unwrap_or("")),
+                    csv_field(&r.plain.as_deref().map(plain_field).unwrap_or_default()),
+                    csv_field(&r.encoding)
+                );
+            }
+        }
+        ExportFormat::Json => {
+            out.push_str("[\n");
+            for (i, r) in records.iter().enumerate() {
+                let _ = write!(
+                    out,
+                    "  {{\"algorithm\": {}, \"hashcat_mode\": {}, \"hash\": {}, \"salt\": {}, \"plain\": {}, \"encoding\": {}}}",
+                    json_string(&r.algorithm),
+                    r.hashcat_mode.map_or_else(|| "null".to_string(), |m| m.to_string()),
+                    json_string(&r.hash),
+                    json_option(r.salt.as_deref()),
+                    json_option(r.plain.as_deref().map(plain_field).as_deref()),
+                    json_string(&r.encoding)
+                );
+                out.push_str(if i + 1 < records.len() { ",\n" } else { "\n" });
+            }
+            out.push_str("]\n");
+        }
+        ExportFormat::HashList => {
+            for r in records {
+                match &r.salt {
+                    Some(salt) => {
+                        let _ = writeln!(out, "{}:{}", r.hashcat_hash(), salt);
+                    }
+                    None => {
+                        let _ = writeln!(out, "{}", r.hashcat_hash());
+                    }
+                }
+            }
+        }
+        ExportFormat::Potfile => {
+            for r in records {
+                let Some(plain) = &r.plain else { continue };
+                match &r.salt {
+                    Some(salt) => {
+                        let _ = writeln!(out, "{}:{}:{}", r.hashcat_hash(), salt, potfile_plain(plain));
+                    }
+                    None => {
+                        let _ = writeln!(out, "{}:{}", r.hashcat_hash(), potfile_plain(plain));
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
+/// 导出菜单的共享设置
+pub struct ExportSettings {
+    pub format: ExportFormat,
+    pub path: String,
+    pub status: Option<Result<String, String>>,
+}
+
+impl Default for ExportSettings {
+    fn default() -> Self {
+        Self { format: ExportFormat::Csv, path: String::new(), status: None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_bytes_rendering() {
+        let gbk = [0xc3, 0xdc, 0xc2, 0xeb];
+        let records = [
+            ExportRecord::from_label("md5", "*00", "", b"pass:word", "UTF-8"),
+            ExportRecord::from_label("md5", "11", "", &gbk, "GBK"),
+            ExportRecord::from_label("md5($pass.$salt)", "22", "s", "密码".as_bytes(), "UTF-8"),
+        ];
+        assert_eq!(
+            render(ExportFormat::Potfile, &records),
+            "00:$HEX[706173733a776f7264]\n11:$HEX[c3dcc2eb]\n22:s:$HEX[e5af86e7a081]\n"
+        );
+        let csv = render(ExportFormat::Csv, &records);
+        assert!(csv.contains(",pass:word,"));
+        assert!(csv.contains(",$HEX[c3dcc2eb],"));
+        assert!(csv.contains(",密码,"));
+        assert!(render(ExportFormat::Json, &records).contains("\"plain\": \"$HEX[c3dcc2eb]\""));
+    }
+
+    #[test]
+    fn hashcat_modes_agree_with_algorithms() {
+        use crate::crypto::{calculate_checksums, calculate_complex_hashes, calculate_slow_hashes, HashAlgorithm, HashParams};
+        let params = HashParams::default();
+        let mut rows = calculate_complex_hashes(b"p", Some("p"), b"s", &HashParams::for_algo(HashAlgorithm::Shake128));
+        rows.extend(calculate_checksums(b"p", &params));
+        rows.extend(calculate_slow_hashes(b"p", b"s"));
+        for (label, _) in &rows {
+            if let Some(algo) = crate::blocks::recipe_algo(label) {
+                assert_eq!(hashcat_mode(label), algo.hashcat_mode(), "{}", label);
+            }
+        }
+        let known = [("md5", 0), ("sha1", 100), ("gost", 6900), ("sha3_256", 17400), ("ntlm", 1000), ("mysql5", 300), ("apr1", 1600)];
+        for (label, mode) in known {
+            assert_eq!(hashcat_mode(label), Some(mode), "{}", label);
+        }
+        assert_eq!(hashcat_mode("crc32"), None);
+    }
+}
@@ -0,0 +1,591 @@
+use crate::crypto::{standard_digest, HashAlgorithm};
+use crate::ntlm;
+use base64::prelude::*;
+use md5::Md5;
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha512};
+
+/// 可识别的完整存储 / 抓包哈希串
+pub enum StoredHash {
+    /// pwdump / secretsdump 行：`user:rid:LM:NT:::`
+    Pwdump {
+        user: String,
+        lm: [u8; 16],
+        nt: [u8; 16],
+    },
+    /// `user::domain:LM响应:NT响应:服务端挑战` (hashcat 5500)
+    NetNtlmV1 {
+        user: String,
+        domain: String,
+        lm_response: [u8; 24],
+        nt_response: [u8; 24],
+        challenge: [u8; 8],
+    },
+    /// `user::domain:服务端挑战:NTProofStr:blob` (hashcat 5600)
+    NetNtlmV2 {
+        user: String,
+        domain: String,
+        challenge: [u8; 8],
+        proof: [u8; 16],
+        blob: Vec<u8>,
+    },
+    /// Django `pbkdf2_sha256$迭代次数$salt$base64`、`pbkdf2_sha1$...`，以及旧版 `sha1$salt$hex` / `md5$salt$hex`
+    Django {
+        algorithm: String,
+        iterations: Option<u32>,
+        salt: String,
+        hash: String,
+    },
+    /// PHPass 可移植格式：`$P$` (WordPress) / `$H$` (phpBB3)，MD5 迭代
+    PhPass { stored: String },
+    /// Drupal 7 `$S$`，SHA-512 迭代，结果截断到 55 个字符
+    Drupal7 { stored: String },
+    /// Joomla 1.x-3.x `md5($pass.$salt):$salt`，盐值 32 位
+    Joomla { hash: String, salt: String },
+    /// vBulletin `md5(md5($pass).$salt):$salt`，盐值 3 位 (3.x) 或 30 位 (4.x/5.x)
+    VBulletin { hash: String, salt: String },
+    /// Discuz! / UCenter `md5(md5($pass).$salt):$salt`，盐值 6 位
+    Discuz { hash: String, salt: String },
+    /// LDAP `{MD5}` / `{SHA}` 等：base64(摘要 + 盐值)，加盐方案的盐值为摘要之后的剩余字节
+    Ldap {
+        scheme: &'static str,
+        algo: HashAlgorithm,
+        digest: Vec<u8>,
+        salt: Vec<u8>,
+    },
+    /// crypt(3) 串 (`$apr1$`、`$1$`、`$5$`、`$6$`、bcrypt 等)，`wrapped` 表示带 LDAP `{CRYPT}` 前缀
+    Crypt { stored: String, wrapped: bool },
+}
+
+/// LDAP 密码方案：(前缀, 摘要算法, 是否加盐)
+const LDAP_SCHEMES: [(&str, HashAlgorithm, bool); 8] = [
+    ("{MD5}", HashAlgorithm::Md5, false),
+    ("{SMD5}", HashAlgorithm::Md5, true),
+    ("{SHA}", HashAlgorithm::Sha1, false),
+    ("{SSHA}", HashAlgorithm::Sha1, true),
+    ("{SHA256}", HashAlgorithm::Sha256, false),
+    ("{SSHA256}", HashAlgorithm::Sha256, true),
+    ("{SHA512}", HashAlgorithm::Sha512, false),
+    ("{SSHA512}", HashAlgorithm::Sha512, true),
+];
+
+/// LDAP 方案编码：前缀 + base64(摘要(口令 + 盐值) + 盐值)，不加盐的方案忽略 `salt`
+pub fn ldap_encode(scheme: &str, password: &[u8], salt: &[u8]) -> String {
+    let Some((tag, algo, salted)) = LDAP_SCHEMES.iter().find(|(tag, ..)| *tag == scheme) else {
+        return String::new();
+    };
+    let salt = if *salted { salt } else { &[] };
+    let mut payload = standard_digest(*algo, &[password, salt].concat());
+    payload.extend_from_slice(salt);
+    format!("{}{}", tag, BASE64_STANDARD.encode(payload))
+}
+
+/// MD5-crypt (`$1$`) 与 Apache 的 `$apr1$` (仅 magic 不同)，盐值最多 8 字节
+pub fn md5_crypt(password: &[u8], salt: &[u8], magic: &str) -> String {
+    let salt = salt.split(|b| *b == b'$').next().unwrap_or_default();
+    let salt = &salt[..salt.len().min(8)];
+    let alternate = Md5::new().chain_update(password).chain_update(salt).chain_update(password).finalize();
+    let mut ctx = Md5::new().chain_update(password).chain_update(magic).chain_update(salt);
+    let mut remaining = password.len();
+    while remaining > 0 {
+        let n = remaining.min(16);
+        ctx.update(&alternate[..n]);
+        remaining -= n;
+    }
+    let mut i = password.len();
+    while i > 0 {
+        ctx.update(if i & 1 == 1 { &[0u8][..] } else { &password[..1] });
+        i >>= 1;
+    }
+    let mut hash = ctx.finalize();
+    for round in 0..1000 {
+        let mut ctx = Md5::new();
+        if round % 2 == 1 {
+            ctx.update(password);
+        } else {
+            ctx.update(hash);
+        }
+        if round % 3 != 0 {
+            ctx.update(salt);
+        }
+        if round % 7 != 0 {
+            ctx.update(password);
+        }
+        if round % 2 == 1 {
+            ctx.update(hash);
+        } else {
+            ctx.update(password);
+        }
+        hash = ctx.finalize();
+    }
+    // 每组 3 字节按大端拼成 24 位，低 6 位先输出
+    let mut encoded = String::new();
+    let groups = [[0, 6, 12], [1, 7, 13], [2, 8, 14], [3, 9, 15], [4, 10, 5]];
+    for [a, b, c] in groups {
+        let value = (hash[a] as u32) << 16 | (hash[b] as u32) << 8 | hash[c] as u32;
+        for i in 0..4 {
+            encoded.push(ITOA64[((value >> (6 * i)) & 0x3f) as usize] as char);
+        }
+    }
+    for i in 0..2 {
+        encoded.push(ITOA64[((hash[11] as u32 >> (6 * i)) & 0x3f) as usize] as char);
+    }
+    format!("{}{}${}", magic, String::from_utf8_lossy(salt), encoded)
+}
+
+/// crypt(3) 串的方案名，无法识别时为 None
+fn crypt_scheme(stored: &str) -> Option<&'static str> {
+    let scheme = match stored {
+        s if s.starts_with("$apr1$") => "Apache APR1-MD5",
+        s if s.starts_with("$1$") => "MD5-crypt",
+        s if s.starts_with("$5$") => "SHA256-crypt",
+        s if s.starts_with("$6$") => "SHA512-crypt",
+        s if s.starts_with("$2a$") || s.starts_with("$2b$") || s.starts_with("$2y$") => "bcrypt",
+        s if s.starts_with('_') && s.len() == 20 => "BSDi crypt",
+        s if s.len() == 13 && !s.starts_with('$') => "DES crypt",
+        _ => return None,
+    };
+    Some(scheme)
+}
+
+/// 一次口令校验的结果
+pub struct Verification {
+    pub stored: StoredHash,
+    pub computed: String,
+    pub expected: String,
+}
+
+impl Verification {
+    pub fn matched(&self) -> bool {
+        self.computed == self.expected
+    }
+}
+
+/// 识别哈希串并用候选口令重新计算
+pub fn verify(text: &str, password: &str) -> Result<Verification, String> {
+    let stored = StoredHash::parse(text)?;
+    let (computed, expected) = stored.recompute(password);
+    Ok(Verification { stored, computed, expected })
+}
+
+/// LM 哈希为空口令 (或未保存 LM) 时的固定值
+const EMPTY_LM: [u8; 16] = [
+    0xaa, 0xd3, 0xb4, 0x35, 0xb5, 0x14, 0x04, 0xee, 0xaa, 0xd3, 0xb4, 0x35, 0xb5, 0x14, 0x04, 0xee,
+];
+
+fn hex_field<const N: usize>(name: &str, text: &str) -> Result<[u8; N], String> {
+    let bytes = hex::decode(text).map_err(|_| format!("{} 不是合法的十六进制", name))?;
+    <[u8; N]>::try_from(bytes.as_slice()).map_err(|_| format!("{} 应为 {} 位十六进制，当前为 {} 位", name, N * 2, text.len()))
+}
+
+/// PHPass 使用的 base64 字母表 (与标准 base64 顺序不同)
+const ITOA64: &[u8; 64] = b"./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// PHPass 的 base64 变体：每 3 字节小端拼接后按 6 位输出，不足 3 字节时少输出一个字符
+fn encode64(input: &[u8]) -> String {
+    let mut out = String::new();
+    for chunk in input.chunks(3) {
+        let value = chunk.iter().enumerate().fold(0u32, |v, (i, b)| v | (*b as u32) << (8 * i));
+        for i in 0..=chunk.len() {
+            out.push(ITOA64[((value >> (6 * i)) & 0x3f) as usize] as char);
+        }
+    }
+    out
+}
+
+/// 迭代类格式允许的最大计算量，防止构造的哈希串让校验长时间占用 CPU
+const MAX_PBKDF2_ITERATIONS: u32 = 10_000_000;
+const MAX_PORTABLE_LOG2: u32 = 24;
+const MAX_BCRYPT_COST: u32 = 16;
+const MAX_SHA_CRYPT_ROUNDS: u64 = 10_000_000;
+
+/// PHPass / Drupal 的迭代次数标记 (第 4 个字符，2 的幂)。规范允许到 2^30，这里限制为 2^24
+fn portable_rounds_log2(stored: &str) -> Option<u32> {
+    let log2 = ITOA64.iter().position(|c| Some(c) == stored.as_bytes().get(3))? as u32;
+    (7..=MAX_PORTABLE_LOG2).contains(&log2).then_some(log2)
+}
+
+/// bcrypt 成本与 SHA-crypt 轮数超过上限时返回错误
+fn check_crypt_cost(stored: &str) -> Result<(), String> {
+    let fields: Vec<&str> = stored.split('$').collect();
+    match fields.as_slice() {
+        ["", "2a" | "2b" | "2y", cost, ..] => {
+            let cost: u32 = cost.parse().map_err(|_| "bcrypt 成本不是数字".to_string())?;
+            if cost > MAX_BCRYPT_COST {
+                return Err(format!("bcrypt 成本 {} 超过上限 {}", cost, MAX_BCRYPT_COST));
+            }
+        }
+        ["", "5" | "6", rounds, ..] if rounds.starts_with("rounds=") => {
+            let rounds: u64 = rounds["rounds=".len()..].parse().map_err(|_| "SHA-crypt 轮数不是数字".to_string())?;
+            if rounds > MAX_SHA_CRYPT_ROUNDS {
+                return Err(format!("SHA-crypt 轮数 {} 超过上限 {}", rounds, MAX_SHA_CRYPT_ROUNDS));
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// hash = D(salt . pass)，再重复 2^N 次 hash = D(hash . pass)，输出 `前 12 位设置 + encode64(hash)` 截断到 `len`
+fn portable_hash<D: Digest>(password: &str, stored: &str, len: usize) -> String {
+    let (Some(setting), Some(log2)) = (stored.get(..12), portable_rounds_log2(stored)) else {
+        return String::new();
+    };
+    let mut hash = D::new().chain_update(&setting[4..]).chain_update(password).finalize();
+    for _ in 0..1u64 << log2 {
+        hash = D::new().chain_update(&hash).chain_update(password).finalize();
+    }
+    let mut out = format!("{}{}", setting, encode64(&hash));
+    out.truncate(len);
+    out
+}
+
+fn md5_hex(data: &str) -> String {
+    hex::encode(Md5::digest(data))
+}
+
+fn is_md5_hex(text: &str) -> bool {
+    text.len() == 32 && text.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+impl StoredHash {
+    /// 按前缀、字段数与长度识别格式
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        // htpasswd 行 `user:hash`，去掉用户名
+        let text = match text.split_once(':') {
+            Some((_, hash)) if hash.starts_with('$') || hash.starts_with('{') => hash,
+            _ => text,
+        };
+        if let Some((tag, algo, salted)) = LDAP_SCHEMES.iter().find(|(tag, ..)| {
+            text.get(..tag.len()).is_some_and(|prefix| prefix.eq_ignore_ascii_case(tag))
+        }) {
+            let encoded = &text[tag.len()..];
+            let payload = BASE64_STANDARD
+                .decode(encoded)
+                .or_else(|_| BASE64_STANDARD_NO_PAD.decode(encoded))
+                .map_err(|_| format!("{} 之后不是合法的 base64", tag))?;
+            let len = standard_digest(*algo, b"").len();
+            if payload.len() < len || (!salted && payload.len() != len) {
+                return Err(format!("{} 解码后应为 {} 字节摘要{}，当前为 {} 字节", tag, len, if *salted { " + 盐值" } else { "" }, payload.len()));
+            }
+            return Ok(StoredHash::Ldap {
+                scheme: tag,
+                algo: *algo,
+                digest: payload[..len].to_vec(),
+                salt: payload[len..].to_vec(),
+            });
+        }
+        let (crypt, wrapped) = match text.get(..7) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("{CRYPT}") => (&text[7..], true),
+            _ => (text, false),
+        };
+        // 不带前缀时只识别以 $ 开头的模块化格式，DES crypt 的 13 位串容易与其他格式混淆
+        if (wrapped || crypt.starts_with('$')) && !crypt.starts_with("$P$") && !crypt.starts_with("$H$") && !crypt.starts_with("$S$") {
+            if crypt_scheme(crypt).is_some() {
+                check_crypt_cost(crypt)?;
+                return Ok(StoredHash::Crypt { stored: crypt.to_string(), wrapped });
+            } else if wrapped {
+                return Err("{CRYPT} 中是无法识别的 crypt 方案".to_string());
+            }
+        }
+        if text.starts_with("$P$") || text.starts_with("$H$") || text.starts_with("$S$") {
+            let expected = if text.starts_with("$S$") { 55 } else { 34 };
+            // 之后按字节位置切分 salt 与哈希，多字节字符会落在字符边界之外
+            if !text.is_ascii() {
+                return Err(format!("{} 格式只能包含 ASCII 字符", &text[..3]));
+            }
+            if text.len() != expected {
+                return Err(format!("{} 格式应为 {} 个字符，当前为 {} 个", &text[..3], expected, text.len()));
+            }
+            if portable_rounds_log2(text).is_none() {
+                return Err(format!("迭代次数标记无效或超过上限 (最多 2^{})", MAX_PORTABLE_LOG2));
+            }
+            let stored = text.to_string();
+            return Ok(if expected == 55 { StoredHash::Drupal7 { stored } } else { StoredHash::PhPass { stored } });
+        }
+        let django: Vec<&str> = text.split('$').collect();
+        match django.as_slice() {
+            [algorithm @ ("pbkdf2_sha256" | "pbkdf2_sha1"), iterations, salt, hash] => {
+                let iterations: u32 = iterations.parse().map_err(|_| "Django 迭代次数不是数字".to_string())?;
+                if !(1..=MAX_PBKDF2_ITERATIONS).contains(&iterations) {
+                    return Err(format!("Django 迭代次数应为 1-{}，当前为 {}", MAX_PBKDF2_ITERATIONS, iterations));
+                }
+                return Ok(StoredHash::Django {
+                    algorithm: algorithm.to_string(),
+                    iterations: Some(iterations),
+                    salt: salt.to_string(),
+                    hash: hash.to_string(),
+                });
+            }
+            [algorithm @ ("sha1" | "md5"), salt, hash] => {
+                return Ok(StoredHash::Django {
+                    algorithm: algorithm.to_string(),
+                    iterations: None,
+                    salt: salt.to_string(),
+                    hash: hash.to_ascii_lowercase(),
+                });
+            }
+            _ => {}
+        }
+        // hash:salt 形式按盐值长度区分 (vBulletin 的盐值可能包含冒号，因此只切分第一个)
+        if let Some((hash, salt)) = text.split_once(':') {
+            if is_md5_hex(hash) {
+                let (hash, salt) = (hash.to_ascii_lowercase(), salt.to_string());
+                match salt.chars().count() {
+                    32 => return Ok(StoredHash::Joomla { hash, salt }),
+                    3 | 30 => return Ok(StoredHash::VBulletin { hash, salt }),
+                    6 => return Ok(StoredHash::Discuz { hash, salt }),
+                    _ => {}
+                }
+            }
+        }
+        let fields: Vec<&str> = text.split(':').collect();
+        match fields.as_slice() {
+            [user, "", domain, a, b, c] if a.len() == 48 && b.len() == 48 => Ok(StoredHash::NetNtlmV1 {
+                user: user.to_string(),
+                domain: domain.to_string(),
+                lm_response: hex_field("LM 响应", a)?,
+                nt_response: hex_field("NT 响应", b)?,
+                challenge: hex_field("服务端挑战", c)?,
+            }),
+            [user, "", domain, a, b, c] => Ok(StoredHash::NetNtlmV2 {
+                user: user.to_string(),
+                domain: domain.to_string(),
+                challenge: hex_field("服务端挑战", a)?,
+                proof: hex_field("NTProofStr", b)?,
+                blob: hex::decode(c).map_err(|_| "blob 不是合法的十六进制".to_string())?,
+            }),
+            [user, _rid, lm, nt, "", "", ""] => Ok(StoredHash::Pwdump {
+                user: user.to_string(),
+                lm: hex_field("LM 哈希", lm)?,
+                nt: hex_field("NT 哈希", nt)?,
+            }),
+            _ => Err("无法识别的格式".to_string()),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            StoredHash::Pwdump { .. } => "pwdump (LM / NT)",
+            StoredHash::NetNtlmV1 { .. } if self.is_ess() => "NetNTLMv1-ESS",
+            StoredHash::NetNtlmV1 { .. } => "NetNTLMv1",
+            StoredHash::NetNtlmV2 { .. } => "NetNTLMv2",
+            StoredHash::Django { .. } => "Django",
+            StoredHash::PhPass { stored } if stored.starts_with("$H$") => "PHPass $H$ (phpBB3)",
+            StoredHash::PhPass { .. } => "PHPass $P$ (WordPress)",
+            StoredHash::Drupal7 { .. } => "Drupal 7 $S$",
+            StoredHash::Joomla { .. } => "Joomla md5($pass.$salt)",
+            StoredHash::VBulletin { .. } => "vBulletin md5(md5($pass).$salt)",
+            StoredHash::Discuz { .. } => "Discuz! md5(md5($pass).$salt)",
+            StoredHash::Ldap { scheme, .. } => scheme,
+            StoredHash::Crypt { stored, .. } => crypt_scheme(stored).unwrap_or("crypt"),
+        }
+    }
+
+    /// NetNTLMv1 的 LM 响应后 16 字节为零时表示使用了扩展会话安全，前 8 字节为客户端挑战
+    fn is_ess(&self) -> bool {
+        match self {
+            StoredHash::NetNtlmV1 { lm_response, .. } => lm_response[8..].iter().all(|b| *b == 0),
+            _ => false,
+        }
+    }
+
+    /// 解析出的各字段，用于界面展示
+    pub fn fields(&self) -> Vec<(&'static str, String)> {
+        match self {
+            StoredHash::Pwdump { user, lm, nt } => vec![
+                ("用户名", user.clone()),
+                ("LM 哈希", hex::encode(lm)),
+                ("NT 哈希", hex::encode(nt)),
+            ],
+            StoredHash::NetNtlmV1 { user, domain, lm_response, nt_response, challenge } => {
+                let mut fields = vec![
+                    ("用户名", user.clone()),
+                    ("域", domain.clone()),
+                    ("服务端挑战", hex::encode(challenge)),
+                ];
+                if self.is_ess() {
+                    fields.push(("客户端挑战", hex::encode(&lm_response[..8])));
+                } else {
+                    fields.push(("LM 响应", hex::encode(lm_response)));
+                }
+                fields.push(("NT 响应", hex::encode(nt_response)));
+                fields
+            }
+            StoredHash::NetNtlmV2 { user, domain, challenge, proof, blob } => vec![
+                ("用户名", user.clone()),
+                ("域", domain.clone()),
+                ("服务端挑战", hex::encode(challenge)),
+                ("NTProofStr", hex::encode(proof)),
+                ("blob", format!("{} 字节", blob.len())),
+            ],
+            StoredHash::Django { algorithm, iterations, salt, hash } => {
+                let mut fields = vec![("算法", algorithm.clone())];
+                if let Some(iterations) = iterations {
+                    fields.push(("迭代次数", iterations.to_string()));
+                }
+                fields.push(("salt", salt.clone()));
+                fields.push(("哈希", hash.clone()));
+                fields
+            }
+            StoredHash::PhPass { stored } | StoredHash::Drupal7 { stored } => {
+                let log2 = portable_rounds_log2(stored).unwrap_or_default();
+                vec![
+                    ("迭代次数", format!("2^{} = {}", log2, 1u64 << log2)),
+                    ("salt", stored[4..12].to_string()),
+                    ("哈希", stored[12..].to_string()),
+                ]
+            }
+            StoredHash::Joomla { hash, salt } | StoredHash::VBulletin { hash, salt } | StoredHash::Discuz { hash, salt } => {
+                vec![("哈希", hash.clone()), ("salt", salt.clone())]
+            }
+            StoredHash::Ldap { algo, digest, salt, .. } => {
+                let mut fields = vec![("摘要算法", algo.name().to_string()), ("摘要", hex::encode(digest))];
+                if !salt.is_empty() {
+                    let text = match std::str::from_utf8(salt) {
+                        Ok(text) => format!("{} ({:?})", hex::encode(salt), text),
+                        Err(_) => hex::encode(salt),
+                    };
+                    fields.push(("salt", text));
+                }
+                fields
+            }
+            StoredHash::Crypt { stored, wrapped } => {
+                let mut fields = vec![("方案", crypt_scheme(stored).unwrap_or("crypt").to_string())];
+                if *wrapped {
+                    fields.push(("包装", "LDAP {CRYPT}".to_string()));
+                }
+                fields.push(("crypt 串", stored.clone()));
+                fields
+            }
+        }
+    }
+
+    /// 用候选口令重新计算，返回 (计算值, 存储值)，两者编码一致，相等即口令正确
+    pub fn recompute(&self, password: &str) -> (String, String) {
+        let nt = ntlm::nt_hash(password);
+        match self {
+            StoredHash::Pwdump { lm, nt: stored_nt, .. } => {
+                // 未保存 LM 时只比较 NT
+                if *lm == EMPTY_LM {
+                    (hex::encode(nt), hex::encode(stored_nt))
+                } else {
+                    let computed = [ntlm::lm_hash(password), nt].concat();
+                    (hex::encode(computed), hex::encode([*lm, *stored_nt].concat()))
+                }
+            }
+            StoredHash::NetNtlmV1 { lm_response, nt_response, challenge, .. } => {
+                let challenge = if self.is_ess() {
+                    let mut client = [0u8; 8];
+                    client.copy_from_slice(&lm_response[..8]);
+                    ntlm::ess_challenge(challenge, &client)
+                } else {
+                    *challenge
+                };
+                (hex::encode(ntlm::netntlmv1_response(&nt, &challenge)), hex::encode(nt_response))
+            }
+            StoredHash::NetNtlmV2 { user, domain, challenge, proof, blob } => {
+                let v2_hash = ntlm::ntlmv2_hash(&nt, user, domain);
+                (hex::encode(ntlm::ntproofstr(&v2_hash, challenge, blob)), hex::encode(proof))
+            }
+            StoredHash::Django { algorithm, iterations, salt, hash } => {
+                let computed = match (algorithm.as_str(), iterations) {
+                    ("pbkdf2_sha256", Some(rounds)) => {
+                        let mut out = [0u8; 32];
+                        pbkdf2::pbkdf2_hmac::<Sha256>(password.as_bytes(), salt.as_bytes(), *rounds, &mut out);
+                        BASE64_STANDARD.encode(out)
+                    }
+                    ("pbkdf2_sha1", Some(rounds)) => {
+                        let mut out = [0u8; 20];
+                        pbkdf2::pbkdf2_hmac::<Sha1>(password.as_bytes(), salt.as_bytes(), *rounds, &mut out);
+                        BASE64_STANDARD.encode(out)
+                    }
+                    ("sha1", None) => hex::encode(Sha1::new().chain_update(salt).chain_update(password).finalize()),
+                    _ => md5_hex(&format!("{}{}", salt, password)),
+                };
+                (computed, hash.clone())
+            }
+            StoredHash::PhPass { stored } => (portable_hash::<Md5>(password, stored, 34), stored.clone()),
+            StoredHash::Drupal7 { stored } => (portable_hash::<Sha512>(password, stored, 55), stored.clone()),
+            StoredHash::Joomla { hash, salt } => (md5_hex(&format!("{}{}", password, salt)), hash.clone()),
+            StoredHash::VBulletin { hash, salt } | StoredHash::Discuz { hash, salt } => {
+                (md5_hex(&format!("{}{}", md5_hex(password), salt)), hash.clone())
+            }
+            StoredHash::Ldap { scheme, digest, salt, .. } => {
+                let stored = format!("{}{}", scheme, BASE64_STANDARD.encode([&digest[..], salt].concat()));
+                (ldap_encode(scheme, password.as_bytes(), salt), stored)
+            }
+            StoredHash::Crypt { stored, .. } => {
+                let md5_magic = ["$apr1$", "$1$"].into_iter().find(|magic| stored.starts_with(magic));
+                let computed = if let Some(magic) = md5_magic {
+                    md5_crypt(password.as_bytes(), &stored.as_bytes()[magic.len()..], magic)
+                } else {
+                    pwhash::unix::crypt(password, stored).unwrap_or_default()
+                };
+                (computed, stored.clone())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// hashcat 示例哈希 (口令均为 "hashcat")
+    #[test]
+    fn hashcat_examples() {
+        let examples = [
+            "$P$984478476IagS59wHZvyQMArzfx58u.",
+            "pbkdf2_sha256$20000$H0dPx8NeajVu$GiC4k5kqbbR9qWBlsRgDywNqC2vd9kqfk7zdorEnNas=",
+        ];
+        for stored in examples {
+            let verification = verify(stored, "hashcat").unwrap();
+            assert!(verification.matched(), "{}: {}", stored, verification.computed);
+            assert!(!verify(stored, "hashcat!").unwrap().matched());
+        }
+    }
+
+    #[test]
+    fn md5_crypt_examples() {
+        // hashcat 示例 500 / 1600，口令 "hashcat"
+        assert_eq!(md5_crypt(b"hashcat", b"28772684", "$1$"), "$1$28772684$iEwNOgGugqO9.bIz5sk8k/");
+        assert_eq!(md5_crypt(b"hashcat", b"71850310", "$apr1$"), "$apr1$71850310$gh9m4xcAn3MGxogwX/ztb.");
+        // 盐值截断到 8 字节，遇到 $ 结束
+        assert_eq!(md5_crypt(b"hashcat", b"28772684extra", "$1$"), "$1$28772684$iEwNOgGugqO9.bIz5sk8k/");
+        assert_eq!(md5_crypt(b"hashcat", b"28772684$iEwNOg", "$1$"), "$1$28772684$iEwNOgGugqO9.bIz5sk8k/");
+    }
+
+    #[test]
+    fn ldap_round_trip() {
+        let encoded = ldap_encode("{SSHA}", b"secret", b"salt");
+        let verification = verify(&encoded, "secret").unwrap();
+        assert!(verification.matched());
+        assert_eq!(ldap_encode("{SHA}", b"password", b"ignored"), "{SHA}W6ph5Mm5Pz8GgiULbPgzG37mj9g=");
+    }
+
+    #[test]
+    fn excessive_cost_is_rejected() {
+        assert!(verify("pbkdf2_sha256$4000000000$salt$AAAA", "x").is_err());
+        assert!(verify("pbkdf2_sha256$0$salt$AAAA", "x").is_err());
+        // 第 4 个字符 'S' 对应 2^30 轮
+        assert!(verify("$P$S84478476IagS59wHZvyQMArzfx58u.", "x").is_err());
+        assert!(verify("$2y$31$abcdefghijklmnopqrstuuabcdefghijklmnopqrstuvwxyz01234", "x").is_err());
+        assert!(verify("$6$rounds=999999999$salt$hash", "x").is_err());
+    }
+
+    #[test]
+    fn portable_hash_rejects_multibyte() {
+        // 34 字节，但 'é' 跨越第 12 字节 (salt 与哈希的分界)
+        let text = format!("$P$94447847é{}", "a".repeat(21));
+        assert_eq!(text.len(), 34);
+        assert!(StoredHash::parse(&text).is_err());
+        let drupal = format!("$S$C4447847é{}", "a".repeat(42));
+        assert_eq!(drupal.len(), 55);
+        assert!(StoredHash::parse(&drupal).is_err());
+        // 合法串的字段照常拆分
+        let fields = StoredHash::parse("$P$984478476IagS59wHZvyQMArzfx58u.").unwrap().fields();
+        assert!(fields.contains(&("salt", "84478476".to_string())));
+    }
+}
@@ -0,0 +1,219 @@
+use std::ops::Range;
+
+use crate::crypto::InputEncoding;
+use crate::export::ExportRecord;
+use crate::potfile::PotEntry;
+
+/// 单次推算最多收集的匹配数，超出后停止搜索
+pub const MATCH_LIMIT: usize = 500;
+
+/// 盐值来源：用户给出的盐值比爆破出来的更可信
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaltSource {
+    None,
+    User,
+    Common,
+    Dictionary,
+    Numeric,
+}
+
+impl SaltSource {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SaltSource::None => "无盐",
+            SaltSource::User => "用户输入",
+            SaltSource::Common => "常见盐值",
+            SaltSource::Dictionary => "自定义字典",
+            SaltSource::Numeric => "数字爆破",
+        }
+    }
+
+    fn score(&self) -> u32 {
+        match self {
+            SaltSource::None => 15,
+            SaltSource::User => 12,
+            SaltSource::Common => 8,
+            SaltSource::Dictionary => 6,
+            SaltSource::Numeric => 3,
+        }
+    }
+}
+
+/// 配方常见程度 (0-20)，依据各类系统与泄露库中出现的频率粗略估计
+fn recipe_popularity(label: &str) -> u32 {
+    match label {
+        "md5" | "sha1" => 20,
+        "ntlm" | "sha256" | "md5(md5($pass))" | "mysql5" => 17,
+        "md5($pass.$salt)" | "md5($salt.$pass)" | "md5(md5($pass).$salt)" | "sha1($pass.$salt)" | "sha1($salt.$pass)" => 15,
+        "sha512" | "md5_middle" | "sha256($pass.$salt)" | "sha256($salt.$pass)" | "sm3" => 12,
+        "lm" | "md5(unicode)" | "md5(md5(md5($pass)))" | "sha1(md5($pass))" | "md5(sha1($pass))" => 10,
+        label if label.starts_with("md5") || label.starts_with("sha") => 7,
+        _ => 4,
+    }
+}
+
+/// 配方结果是否依赖盐值：标签含 `$salt`，或 LDAP 加盐格式与 apr1 (盐值嵌在结果中)
+pub fn uses_salt(label: &str) -> bool {
+    label.contains("$salt") || label.starts_with("ldap_ssha") || label == "apr1"
+}
+
+/// 目标与计算结果的重合方式，区间为较长一方中被匹配到的字节范围
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Overlap {
+    Exact,
+    /// 计算结果中包含目标 (目标是截断的哈希)
+    HashContainsTarget(Range<usize>),
+    /// 目标中包含计算结果 (目标带前后缀或拼接)
+    TargetContainsHash(Range<usize>),
+}
+
+impl Overlap {
+    /// 精确匹配为 `Exact`；模糊模式下一方包含另一方时记录位置，过短的子串不计
+    pub fn find(hash: &str, target: &str, fuzzy: bool) -> Option<Self> {
+        if hash == target {
+            return Some(Overlap::Exact);
+        }
+        if !fuzzy || hash.len().min(target.len()) < 8 {
+            return None;
+        }
+        if let Some(start) = hash.find(target) {
+            Some(Overlap::HashContainsTarget(start..start + target.len()))
+        } else {
+            target.find(hash).map(|start| Overlap::TargetContainsHash(start..start + hash.len()))
+        }
+    }
+
+    /// 吻合程度 (百分比)：较短一方占较长一方的比例
+    pub fn quality(&self, hash_len: usize, target_len: usize) -> u32 {
+        match self {
+            Overlap::Exact => 100,
+            Overlap::HashContainsTarget(span) => (span.len() * 100 / hash_len.max(1)) as u32,
+            Overlap::TargetContainsHash(span) => (span.len() * 100 / target_len.max(1)) as u32,
+        }
+    }
+}
+
+/// 一条推算匹配
+pub struct InferenceMatch {
+    /// 内置配方标识，即批量结果中的标签 (如 `md5($salt.md5($pass))`)
+    pub recipe: String,
+    pub hash: String,
+    pub salt: String,
+    pub salt_source: SaltSource,
+    pub charset: InputEncoding,
+    pub overlap: Overlap,
+    /// 吻合程度 (百分比)，100 为精确匹配
+    pub quality: u32,
+    pub score: u32,
+}
+
+impl InferenceMatch {
+    pub fn new(recipe: &str, hash: &str, salt: &str, salt_source: SaltSource, charset: InputEncoding, overlap: Overlap, target_len: usize) -> Self {
+        let quality = overlap.quality(hash.len(), target_len);
+        let exactness = if overlap == Overlap::Exact { 50 } else { quality * 30 / 100 };
+        let charset_score = if charset == InputEncoding::Utf8 { 5 } else { 2 };
+        // 盐值越长越不像是人为设定的简单盐值
+        let salt_penalty = (salt.len() as u32).min(10) / 2;
+        let score = (exactness + recipe_popularity(recipe) + salt_source.score() + charset_score).saturating_sub(salt_penalty);
+        Self {
+            recipe: recipe.to_string(),
+            hash: hash.to_string(),
+            salt: salt.to_string(),
+            salt_source,
+            charset,
+            overlap,
+            quality,
+            score,
+        }
+    }
+
+    pub fn is_exact(&self) -> bool {
+        self.overlap == Overlap::Exact
+    }
+
+    /// 导出记录中的明文为按本条匹配的字符集编码后的字节
+    pub fn to_export(&self, plain: &str) -> ExportRecord {
+        let bytes = self.charset.encode_text(plain).unwrap_or_else(|_| plain.as_bytes().to_vec());
+        ExportRecord::from_label(&self.recipe, &self.hash, &self.salt, &bytes, self.charset.name())
+    }
+}
+
+/// 推算过程产生的一条结果
+pub enum InferenceResult {
+    Match(InferenceMatch),
+    /// potfile 中已有的破解记录
+    Known(PotEntry),
+    Info(String),
+    Warning(String),
+    Error(String),
+}
+
+impl InferenceResult {
+    pub fn as_match(&self) -> Option<&InferenceMatch> {
+        match self {
+            InferenceResult::Match(m) => Some(m),
+            _ => None,
+        }
+    }
+}
+
+/// 匹配列表的排序方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InferenceSort {
+    Score,
+    Recipe,
+    Salt,
+}
+
+impl InferenceSort {
+    pub fn all() -> &'static [InferenceSort] {
+        &[InferenceSort::Score, InferenceSort::Recipe, InferenceSort::Salt]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            InferenceSort::Score => "按得分",
+            InferenceSort::Recipe => "按配方",
+            InferenceSort::Salt => "按盐值",
+        }
+    }
+
+    pub fn sort(&self, matches: &mut [&InferenceMatch]) {
+        match self {
+            InferenceSort::Score => matches.sort_by_key(|m| std::cmp::Reverse(m.score)),
+            InferenceSort::Recipe => matches.sort_by(|a, b| a.recipe.cmp(&b.recipe).then(b.score.cmp(&a.score))),
+            InferenceSort::Salt => matches.sort_by(|a, b| a.salt.cmp(&b.salt).then(b.score.cmp(&a.score))),
+        }
+    }
+}
+
+/// 按配方分组，组内按得分降序，组之间按组内最高分降序
+pub fn ranked_groups<'a>(matches: impl IntoIterator<Item = &'a InferenceMatch>) -> Vec<(&'a str, Vec<&'a InferenceMatch>)> {
+    let mut groups: Vec<(&str, Vec<&InferenceMatch>)> = Vec::new();
+    for m in matches {
+        match groups.iter_mut().find(|(recipe, _)| *recipe == m.recipe) {
+            Some((_, items)) => items.push(m),
+            None => groups.push((&m.recipe, vec![m])),
+        }
+    }
+    for (_, items) in &mut groups {
+        items.sort_by_key(|m| std::cmp::Reverse(m.score));
+    }
+    groups.sort_by_key(|(_, items)| std::cmp::Reverse(items[0].score));
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn salted_recipes() {
+        for label in ["md5($pass.$salt)", "sm3($salt.$pass.$salt)", "ldap_ssha256", "apr1"] {
+            assert!(uses_salt(label), "{}", label);
+        }
+        for label in ["md5", "md5(md5($pass))", "ldap_sha", "ldap_md5", "ntlm", "mysql5"] {
+            assert!(!uses_salt(label), "{}", label);
+        }
+    }
+}
@@ -0,0 +1,570 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread;
+
+use crate::crypto::{standard_digest, HashAlgorithm};
+
+/// 查找表文件头：魔数 + 配方编号 + 摘要长度 + 条目数
+const MAGIC: &[u8; 6] = b"HETBL1";
+const HEADER_LEN: u64 = 16;
+
+/// 可建表的无盐快速配方
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableRecipe {
+    Md5,
+    Sha1,
+    Ntlm,
+    Md5Md5,
+}
+
+impl TableRecipe {
+    pub fn all() -> &'static [TableRecipe] {
+        &[TableRecipe::Md5, TableRecipe::Sha1, TableRecipe::Ntlm, TableRecipe::Md5Md5]
+    }
+
+    /// 与批量结果中的标签一致
+    pub fn name(&self) -> &'static str {
+        match self {
+            TableRecipe::Md5 => "md5",
+            TableRecipe::Sha1 => "sha1",
+            TableRecipe::Ntlm => "ntlm",
+            TableRecipe::Md5Md5 => "md5(md5($pass))",
+        }
+    }
+
+    fn id(&self) -> u8 {
+        match self {
+            TableRecipe::Md5 => 1,
+            TableRecipe::Sha1 => 2,
+            TableRecipe::Ntlm => 3,
+            TableRecipe::Md5Md5 => 4,
+        }
+    }
+
+    fn from_id(id: u8) -> Option<Self> {
+        TableRecipe::all().iter().copied().find(|r| r.id() == id)
+    }
+
+    pub fn digest_len(&self) -> usize {
+        match self {
+            TableRecipe::Sha1 => 20,
+            _ => 16,
+        }
+    }
+
+    /// NTLM 需要把口令转为 UTF-16，非 UTF-8 的口令无法计算，返回 None
+    pub fn compute(&self, pass: &[u8]) -> Option<Vec<u8>> {
+        Some(match self {
+            TableRecipe::Md5 => standard_digest(HashAlgorithm::Md5, pass),
+            TableRecipe::Sha1 => standard_digest(HashAlgorithm::Sha1, pass),
+            TableRecipe::Ntlm => crate::ntlm::nt_hash(std::str::from_utf8(pass).ok()?).to_vec(),
+            TableRecipe::Md5Md5 => {
+                let inner = hex::encode(standard_digest(HashAlgorithm::Md5, pass));
+                standard_digest(HashAlgorithm::Md5, inner.as_bytes())
+            }
+        })
+    }
+}
+
+/// 磁盘上的有序查找表。
+/// `<path>` 存放按摘要排序的定长条目 (摘要 + 口令在 `<path>.words` 中的偏移)，
+/// 查询时二分查找、按需读取，不把整张表载入内存。
+pub struct LookupTable {
+    pub path: PathBuf,
+    pub recipe: TableRecipe,
+    pub entries: u64,
+    file: File,
+    words: File,
+}
+
+/// 在完整文件名后追加后缀 (md5.tbl -> md5.tbl.words)，避免替换掉原扩展名
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+fn words_path(path: &Path) -> PathBuf {
+    with_suffix(path, ".words")
+}
+
+fn tmp_path(path: &Path) -> PathBuf {
+    with_suffix(path, ".tmp")
+}
+
+fn io_err(context: &str) -> impl Fn(std::io::Error) -> String + '_ {
+    move |e| format!("{}: {}", context, e)
+}
+
+fn read_header(file: &mut File) -> Result<(TableRecipe, u64), String> {
+    let mut header = [0u8; HEADER_LEN as usize];
+    file.seek(SeekFrom::Start(0)).and_then(|_| file.read_exact(&mut header)).map_err(io_err("读取表头失败"))?;
+    if &header[..6] != MAGIC {
+        return Err("不是查找表文件".to_string());
+    }
+    let recipe = TableRecipe::from_id(header[6]).ok_or("未知的查找表配方")?;
+    if header[7] as usize != recipe.digest_len() {
+        return Err("查找表摘要长度与配方不符".to_string());
+    }
+    let entries = u64::from_le_bytes(header[8..16].try_into().expect("8 字节"));
+    Ok((recipe, entries))
+}
+
+fn write_header(out: &mut impl Write, recipe: TableRecipe, entries: u64) -> std::io::Result<()> {
+    out.write_all(MAGIC)?;
+    out.write_all(&[recipe.id(), recipe.digest_len() as u8])?;
+    out.write_all(&entries.to_le_bytes())
+}
+
+impl LookupTable {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let mut file = File::open(path).map_err(io_err("无法打开查找表"))?;
+        let words = File::open(words_path(path)).map_err(io_err("无法打开口令文件"))?;
+        let (recipe, entries) = read_header(&mut file)?;
+        Ok(Self { path: path.to_path_buf(), recipe, entries, file, words })
+    }
+
+    fn entry_len(&self) -> u64 {
+        self.recipe.digest_len() as u64 + 8
+    }
+
+    /// 表文件与口令文件的总字节数
+    pub fn size_on_disk(&self) -> u64 {
+        let len = |f: &File| f.metadata().map(|m| m.len()).unwrap_or(0);
+        len(&self.file) + len(&self.words)
+    }
+
+    fn read_entry(&mut self, index: u64) -> Result<(Vec<u8>, u64), String> {
+        let mut buf = vec![0u8; self.entry_len() as usize];
+        self.file
+            .seek(SeekFrom::Start(HEADER_LEN + index * self.entry_len()))
+            .and_then(|_| self.file.read_exact(&mut buf))
+            .map_err(io_err("读取查找表失败"))?;
+        let offset = u64::from_le_bytes(buf[self.recipe.digest_len()..].try_into().expect("8 字节"));
+        buf.truncate(self.recipe.digest_len());
+        Ok((buf, offset))
+    }
+
+    fn read_word(&mut self, offset: u64) -> Result<Vec<u8>, String> {
+        let mut len = [0u8; 2];
+        self.words.seek(SeekFrom::Start(offset)).and_then(|_| self.words.read_exact(&mut len)).map_err(io_err("读取口令失败"))?;
+        let mut word = vec![0u8; u16::from_le_bytes(len) as usize];
+        self.words.read_exact(&mut word).map_err(io_err("读取口令失败"))?;
+        Ok(word)
+    }
+
+    /// 二分查找摘要，返回对应口令
+    pub fn lookup(&mut self, target: &[u8]) -> Result<Option<String>, String> {
+        if target.len() != self.recipe.digest_len() {
+            return Ok(None);
+        }
+        let (mut lo, mut hi) = (0, self.entries);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            let (hash, offset) = self.read_entry(mid)?;
+            match hash.as_slice().cmp(target) {
+                std::cmp::Ordering::Less => lo = mid + 1,
+                std::cmp::Ordering::Greater => hi = mid,
+                std::cmp::Ordering::Equal => {
+                    return Ok(Some(String::from_utf8_lossy(&self.read_word(offset)?).into_owned()));
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+pub enum TableEvent {
+    Progress { phase: &'static str, done: u64, total: u64 },
+    /// `skipped`：配方无法计算而跳过的口令数 (如 NTLM 遇到非 UTF-8 口令)
+    Finished { added: u64, entries: u64, skipped: u64 },
+    Failed(String),
+}
+
+/// 后台建表 / 追加任务
+pub struct TableJob {
+    rx: Receiver<TableEvent>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl TableJob {
+    /// 用字典 `wordlist` 创建查找表，`path` 已存在时追加 (配方必须一致)
+    pub fn start(path: PathBuf, recipe: TableRecipe, wordlist: PathBuf) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let flag = cancel.clone();
+        thread::spawn(move || {
+            if let Err(e) = build(&path, recipe, &wordlist, &tx, &flag) {
+                let _ = tx.send(TableEvent::Failed(e));
+            }
+        });
+        Self { rx, cancel }
+    }
+
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    pub fn poll(&self) -> Vec<TableEvent> {
+        self.rx.try_iter().collect()
+    }
+}
+
+/// 每个排序段最多驻留内存的条目数，超出后排序写入临时文件，最后多路归并
+const RUN_ENTRIES: usize = 1 << 20;
+
+/// 顺序读取已有表的下一条定长条目
+fn next_old(reader: &mut Option<BufReader<File>>, remaining: &mut u64, entry_len: usize) -> Result<Option<Vec<u8>>, String> {
+    match reader {
+        Some(r) if *remaining > 0 => {
+            let mut entry = vec![0u8; entry_len];
+            r.read_exact(&mut entry).map_err(io_err("读取查找表失败"))?;
+            *remaining -= 1;
+            Ok(Some(entry))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// 临时排序段：按摘要排序的 `摘要 + 口令长度 (u16) + 口令` 记录
+struct Run {
+    reader: BufReader<File>,
+    digest_len: usize,
+}
+
+impl Run {
+    fn write(path: &Path, entries: &mut Vec<Entry>) -> Result<(), String> {
+        entries.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        entries.dedup_by(|a, b| a.0 == b.0);
+        let mut out = BufWriter::new(File::create(path).map_err(io_err("无法创建临时文件"))?);
+        for (hash, word) in entries.drain(..) {
+            out.write_all(&hash)
+                .and_then(|_| out.write_all(&(word.len() as u16).to_le_bytes()))
+                .and_then(|_| out.write_all(&word))
+                .map_err(io_err("写入临时文件失败"))?;
+        }
+        out.flush().map_err(io_err("写入临时文件失败"))
+    }
+
+    fn open(path: &Path, digest_len: usize) -> Result<Self, String> {
+        let file = File::open(path).map_err(io_err("无法打开临时文件"))?;
+        Ok(Self { reader: BufReader::new(file), digest_len })
+    }
+
+    fn next(&mut self) -> Result<Option<Entry>, String> {
+        if self.reader.fill_buf().map_err(io_err("读取临时文件失败"))?.is_empty() {
+            return Ok(None);
+        }
+        let mut hash = vec![0u8; self.digest_len];
+        let mut len = [0u8; 2];
+        self.reader.read_exact(&mut hash).and_then(|_| self.reader.read_exact(&mut len)).map_err(io_err("读取临时文件失败"))?;
+        let mut word = vec![0u8; u16::from_le_bytes(len) as usize];
+        self.reader.read_exact(&mut word).map_err(io_err("读取临时文件失败"))?;
+        Ok(Some((hash, word)))
+    }
+}
+
+fn run_path(path: &Path, index: usize) -> PathBuf {
+    with_suffix(path, &format!(".run{}", index))
+}
+
+fn build(path: &Path, recipe: TableRecipe, wordlist: &Path, tx: &Sender<TableEvent>, cancel: &AtomicBool) -> Result<(), String> {
+    build_with_runs(path, recipe, wordlist, RUN_ENTRIES, tx, cancel)
+}
+
+fn build_with_runs(
+    path: &Path,
+    recipe: TableRecipe,
+    wordlist: &Path,
+    run_entries: usize,
+    tx: &Sender<TableEvent>,
+    cancel: &AtomicBool,
+) -> Result<(), String> {
+    let existing = if path.exists() {
+        let table = LookupTable::open(path)?;
+        if table.recipe != recipe {
+            return Err(format!("已有查找表的配方为 {}，不能追加 {}", table.recipe.name(), recipe.name()));
+        }
+        Some(table)
+    } else {
+        None
+    };
+    // 失败或取消时口令文件截回原长度 (新建时删除)，已有表保持不变
+    let had_table = existing.is_some();
+    let words_len = fs::metadata(words_path(path)).map_or(0, |m| m.len());
+    let mut runs = Vec::new();
+    let result = write_runs(path, recipe, wordlist, run_entries, &mut runs, tx, cancel)
+        .and_then(|(total, skipped)| merge(path, recipe, existing, &runs, total, tx, cancel).map(|(added, entries)| (added, entries, skipped)));
+    for run in &runs {
+        let _ = fs::remove_file(run);
+    }
+    if result.is_err() {
+        let _ = fs::remove_file(tmp_path(path));
+        if !had_table {
+            let _ = fs::remove_file(words_path(path));
+        } else if let Ok(words) = OpenOptions::new().write(true).open(words_path(path)) {
+            let _ = words.set_len(words_len);
+        }
+    }
+    let (added, entries, skipped) = result?;
+    let _ = tx.send(TableEvent::Finished { added, entries, skipped });
+    Ok(())
+}
+
+/// 字典中的一条口令：(摘要, 口令)
+type Entry = (Vec<u8>, Vec<u8>);
+
+/// 第一步：计算字典中每个口令的摘要，每满 `run_entries` 条排序写入一个临时段，返回 (条目总数, 跳过的口令数)
+fn write_runs(
+    path: &Path,
+    recipe: TableRecipe,
+    wordlist: &Path,
+    run_entries: usize,
+    runs: &mut Vec<PathBuf>,
+    tx: &Sender<TableEvent>,
+    cancel: &AtomicBool,
+) -> Result<(u64, u64), String> {
+    let file = File::open(wordlist).map_err(io_err("无法打开字典"))?;
+    let total = file.metadata().map(|m| m.len()).unwrap_or(0);
+    let mut reader = BufReader::new(file);
+    let mut entries: Vec<Entry> = Vec::new();
+    let mut buf = Vec::new();
+    let mut done = 0u64;
+    let mut count = 0u64;
+    let mut skipped = 0u64;
+    loop {
+        if cancel.load(Ordering::Relaxed) {
+            return Err("已取消".to_string());
+        }
+        buf.clear();
+        let read = reader.read_until(b'\n', &mut buf).map_err(io_err("读取字典失败"))?;
+        if read == 0 {
+            break;
+        }
+        done += read as u64;
+        while buf.last().is_some_and(|b| *b == b'\n' || *b == b'\r') {
+            buf.pop();
+        }
+        if buf.is_empty() || buf.len() > u16::MAX as usize {
+            continue;
+        }
+        let Some(hash) = recipe.compute(&buf) else {
+            skipped += 1;
+            continue;
+        };
+        entries.push((hash, buf.clone()));
+        count += 1;
+        if entries.len() >= run_entries {
+            let run = run_path(path, runs.len());
+            runs.push(run.clone());
+            Run::write(&run, &mut entries)?;
+        }
+        if count.is_multiple_of(100_000) {
+            let _ = tx.send(TableEvent::Progress { phase: "计算摘要", done, total });
+        }
+    }
+    if !entries.is_empty() {
+        let run = run_path(path, runs.len());
+        runs.push(run.clone());
+        Run::write(&run, &mut entries)?;
+    }
+    Ok((count, skipped))
+}
+
+/// 第二步：多路归并各临时段，再与已有表归并写入临时表文件，返回 (新增条目数, 总条目数)。
+/// 已存在的摘要不再重复追加口令
+fn merge(
+    path: &Path,
+    recipe: TableRecipe,
+    existing: Option<LookupTable>,
+    runs: &[PathBuf],
+    total: u64,
+    tx: &Sender<TableEvent>,
+    cancel: &AtomicBool,
+) -> Result<(u64, u64), String> {
+    let digest_len = recipe.digest_len();
+    let mut readers = runs.iter().map(|run| Run::open(run, digest_len)).collect::<Result<Vec<_>, _>>()?;
+    // 小顶堆按 (摘要, 段号) 排序，同一摘要取最早出现的口令
+    let mut heap = BinaryHeap::new();
+    for (index, reader) in readers.iter_mut().enumerate() {
+        if let Some((hash, word)) = reader.next()? {
+            heap.push(Reverse((hash, index, word)));
+        }
+    }
+
+    let mut words = OpenOptions::new().create(true).append(true).open(words_path(path)).map_err(io_err("无法打开口令文件"))?;
+    let mut word_offset = words.metadata().map(|m| m.len()).map_err(io_err("无法读取口令文件"))?;
+    let mut words = BufWriter::new(&mut words);
+    let tmp = tmp_path(path);
+    let mut out = BufWriter::new(File::create(&tmp).map_err(io_err("无法创建临时文件"))?);
+    write_header(&mut out, recipe, 0).map_err(io_err("写入失败"))?;
+
+    let old_count = existing.as_ref().map_or(0, |t| t.entries);
+    let mut old_reader = match &existing {
+        Some(_) => {
+            let mut f = BufReader::new(File::open(path).map_err(io_err("无法打开查找表"))?);
+            f.seek(SeekFrom::Start(HEADER_LEN)).map_err(io_err("读取查找表失败"))?;
+            Some(f)
+        }
+        None => None,
+    };
+    let entry_len = digest_len + 8;
+    let mut remaining = old_count;
+    let mut old = next_old(&mut old_reader, &mut remaining, entry_len)?;
+    let mut written = 0u64;
+    let mut added = 0u64;
+    let mut merged = 0u64;
+    let mut last: Option<Vec<u8>> = None;
+    while let Some(Reverse((hash, index, word))) = heap.pop() {
+        if cancel.load(Ordering::Relaxed) {
+            return Err("已取消".to_string());
+        }
+        if let Some(next) = readers[index].next()? {
+            heap.push(Reverse((next.0, index, next.1)));
+        }
+        merged += 1;
+        if merged.is_multiple_of(100_000) {
+            let _ = tx.send(TableEvent::Progress { phase: "归并写入", done: merged, total });
+        }
+        if last.as_ref() == Some(&hash) {
+            continue;
+        }
+        while let Some(entry) = old.take() {
+            if entry[..digest_len] < hash[..] {
+                out.write_all(&entry).map_err(io_err("写入失败"))?;
+                written += 1;
+                old = next_old(&mut old_reader, &mut remaining, entry_len)?;
+            } else {
+                old = Some(entry);
+                break;
+            }
+        }
+        if old.as_ref().is_some_and(|entry| entry[..digest_len] == hash[..]) {
+            last = Some(hash);
+            continue;
+        }
+        words.write_all(&(word.len() as u16).to_le_bytes()).and_then(|_| words.write_all(&word)).map_err(io_err("写入口令失败"))?;
+        out.write_all(&hash).and_then(|_| out.write_all(&word_offset.to_le_bytes())).map_err(io_err("写入失败"))?;
+        word_offset += 2 + word.len() as u64;
+        written += 1;
+        added += 1;
+        last = Some(hash);
+    }
+    while let Some(entry) = old {
+        out.write_all(&entry).map_err(io_err("写入失败"))?;
+        written += 1;
+        old = next_old(&mut old_reader, &mut remaining, entry_len)?;
+    }
+    words.flush().map_err(io_err("写入口令失败"))?;
+    let mut out = out.into_inner().map_err(|e| format!("写入失败: {}", e.error()))?;
+    out.seek(SeekFrom::Start(0)).and_then(|_| write_header(&mut out, recipe, written)).map_err(io_err("写入表头失败"))?;
+    drop(out);
+    drop(old_reader);
+    drop(existing);
+    fs::rename(&tmp, path).map_err(io_err("替换查找表失败"))?;
+    Ok((added, written))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("hash_enum_tool_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn build_from(path: &Path, recipe: TableRecipe, words: &[&str], run_entries: usize) -> Result<(), String> {
+        let wordlist = path.with_extension("txt");
+        fs::write(&wordlist, words.join("\n")).unwrap();
+        let (tx, _rx) = mpsc::channel();
+        build_with_runs(path, recipe, &wordlist, run_entries, &tx, &AtomicBool::new(false))
+    }
+
+    #[test]
+    fn merge_runs_and_lookup() {
+        let dir = temp_dir("merge");
+        let path = dir.join("md5.tbl");
+        let first: Vec<String> = (0..50).map(|i| format!("word{}", i)).collect();
+        let first: Vec<&str> = first.iter().map(String::as_str).collect();
+        // 每段 7 条，产生多个临时段；重复口令只保留一条
+        let mut with_dup = first.clone();
+        with_dup.extend(["word3", "word41"]);
+        build_from(&path, TableRecipe::Md5, &with_dup, 7).unwrap();
+
+        let mut table = LookupTable::open(&path).unwrap();
+        assert_eq!(table.entries, 50);
+        for word in &first {
+            let hash = TableRecipe::Md5.compute(word.as_bytes()).unwrap();
+            assert_eq!(table.lookup(&hash).unwrap().as_deref(), Some(*word));
+        }
+        assert_eq!(table.lookup(&TableRecipe::Md5.compute(b"missing").unwrap()).unwrap(), None);
+        assert_eq!(table.lookup(&[0u8; 20]).unwrap(), None);
+        drop(table);
+
+        // 追加：与已有表归并，已有口令不重复写入
+        let words_len = fs::metadata(words_path(&path)).unwrap().len();
+        build_from(&path, TableRecipe::Md5, &["word0", "extra", "word49", "another"], 3).unwrap();
+        let mut table = LookupTable::open(&path).unwrap();
+        assert_eq!(table.entries, 52);
+        assert_eq!(fs::metadata(words_path(&path)).unwrap().len(), words_len + 2 + 5 + 2 + 7);
+        for word in ["word0", "word25", "extra", "another"] {
+            let hash = TableRecipe::Md5.compute(word.as_bytes()).unwrap();
+            assert_eq!(table.lookup(&hash).unwrap().as_deref(), Some(word));
+        }
+        drop(table);
+
+        // 配方不符时拒绝追加，临时段与表文件保持不变
+        assert!(build_from(&path, TableRecipe::Sha1, &["x"], 3).is_err());
+        assert_eq!(LookupTable::open(&path).unwrap().entries, 52);
+        assert!(!run_path(&path, 0).exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn cancelled_build_keeps_words() {
+        let dir = temp_dir("cancel");
+        let path = dir.join("sha1.tbl");
+        build_from(&path, TableRecipe::Sha1, &["a", "b"], 1).unwrap();
+        let words_len = fs::metadata(words_path(&path)).unwrap().len();
+
+        let wordlist = dir.join("more.txt");
+        fs::write(&wordlist, "c\nd\n").unwrap();
+        let (tx, _rx) = mpsc::channel();
+        let result = build_with_runs(&path, TableRecipe::Sha1, &wordlist, 1, &tx, &AtomicBool::new(true));
+        assert!(result.is_err());
+        assert_eq!(fs::metadata(words_path(&path)).unwrap().len(), words_len);
+        assert_eq!(LookupTable::open(&path).unwrap().entries, 2);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn ntlm_skips_non_utf8_words() {
+        let dir = temp_dir("ntlm");
+        let path = dir.join("ntlm.tbl");
+        // 同目录下的 ntlm.tmp 属于别的文件，建表时不能被覆盖
+        let other = dir.join("ntlm.tmp");
+        fs::write(&other, "keep").unwrap();
+        let wordlist = dir.join("words.txt");
+        fs::write(&wordlist, b"password\n\xff\xfebad\nsecret\n").unwrap();
+        let (tx, rx) = mpsc::channel();
+        build_with_runs(&path, TableRecipe::Ntlm, &wordlist, 2, &tx, &AtomicBool::new(false)).unwrap();
+        assert!(rx.try_iter().any(|e| matches!(e, TableEvent::Finished { added: 2, entries: 2, skipped: 1 })));
+        assert_eq!(fs::read(&other).unwrap(), b"keep");
+        assert!(!tmp_path(&path).exists());
+
+        let mut table = LookupTable::open(&path).unwrap();
+        let hash = TableRecipe::Ntlm.compute(b"password").unwrap();
+        assert_eq!(table.lookup(&hash).unwrap().as_deref(), Some("password"));
+        assert_eq!(TableRecipe::Ntlm.compute(b"\xff\xfebad"), None);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
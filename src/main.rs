@@ -1,9 +1,32 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // Hide console in release mode
 
+mod batch;
+mod blocks;
+mod codegen;
 mod crypto;
+mod ethereum;
+mod evaluator;
+mod export;
+mod formats;
+mod inference;
+mod lookup_table;
+mod ntlm;
+mod potfile;
+
+use std::collections::HashMap;
+use std::thread::JoinHandle;
 
 use eframe::egui;
-use crypto::{HashAlgorithm, SaltMode, calculate_hash, calculate_complex_hashes};
+use batch::{BatchEvent, BatchJob, BatchRecipe, BatchRow, BatchSource, BatchSpec, BATCH_ROW_LIMIT};
+use blocks::{BlockHistory, CustomBlock, Rejoin, TraceNode};
+use codegen::CodeLanguage;
+use evaluator::Evaluator;
+use export::{ExportFormat, ExportRecord, ExportSettings};
+use formats::Verification;
+use inference::{ranked_groups, uses_salt, InferenceMatch, InferenceResult, InferenceSort, Overlap, SaltSource, MATCH_LIMIT};
+use lookup_table::{LookupTable, TableEvent, TableJob, TableRecipe};
+use potfile::{PotEntry, PotIndex};
+use crypto::{decode_target, DisplayFormat, HashAlgorithm, HashParams, InputEncoding, InputFormat, MatchKind, OutputEncoding, XOF_MAX_LEN, calculate_checksums, calculate_complex_hashes, calculate_slow_hashes, omitted_checksums};
 
 fn main() -> eframe::Result<()> {
     let options = eframe::NativeOptions {
@@ -77,39 +100,41 @@ fn setup_custom_style(ctx: &egui::Context) {
     ctx.set_style(style);
 }
 
-#[derive(Clone, PartialEq, Eq)]
-enum CustomBlock {
-    Password,
-    Salt,
-    Literal(String),
-    Hash(HashAlgorithm, Vec<CustomBlock>),
-}
-
-impl CustomBlock {
-    fn name(&self) -> String {
-        match self {
-            CustomBlock::Password => "$pass".to_string(),
-            CustomBlock::Salt => "$salt".to_string(),
-            CustomBlock::Literal(s) => format!("\"{}\"", s),
-            CustomBlock::Hash(algo, _) => format!("{}(...)", algo.name()),
-        }
-    }
-}
-
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum ActiveTool {
     Encryption,
     Inference,
+    Verify,
+    Batch,
+    Lookup,
+    Ethereum,
 }
 
 struct HashApp {
     active_tool: ActiveTool,
     input_text: String,
     salt_text: String,
+    input_format: InputFormat,
+    salt_format: InputFormat,
+    // 按输入编码转换后的字节，转换失败时为错误信息
+    input_bytes: Result<Vec<u8>, String>,
+    salt_bytes: Result<Vec<u8>, String>,
     // salt_mode: SaltMode, // Removed in favor of bulk view
     // selected_algo: HashAlgorithm, // Removed in favor of bulk view
     output_text: String,
+    output_is_binary: bool,
+    custom_trace: Vec<TraceNode>,
+    /// 积木在后台线程计算，大轮数循环块不会卡住输入
+    evaluator: Evaluator,
+    /// 上次提交自检时的积木，未变化时不重复自检
+    checked_blocks: Option<Vec<CustomBlock>>,
     bulk_results: Vec<(String, String)>,
+    xof_params: HashParams,
+    checksum_results: Vec<(String, String)>,
+    checksum_params: HashParams,
+    display_format: DisplayFormat,
+    row_formats: HashMap<String, DisplayFormat>,
+    export_settings: ExportSettings,
     search_query: String,
     compare_target: String,
     auto_calculate: bool,
@@ -117,16 +142,70 @@ struct HashApp {
     inference_plaintext: String,
     inference_target_hash: String,
     inference_salt: String,
-    inference_results: Vec<String>,
+    inference_results: Vec<InferenceResult>,
+    inference_sort: InferenceSort,
+    inference_grouped: bool,
+    inference_filter: String,
+    inference_exact_only: bool,
     inference_fuzzy: bool,
     inference_brute_salt: bool,
+    inference_charsets: bool,
     inference_use_custom_dict: bool,
     inference_custom_dict_path: String,
+    // 口令校验工具
+    verify_stored: String,
+    verify_password: String,
+    verify_result: Option<Result<Verification, String>>,
+    /// PBKDF2 / PHPass / bcrypt 等迭代格式较慢，在后台线程校验
+    verify_running: Option<JoinHandle<Result<Verification, String>>>,
+    // 批量计算
+    batch_use_file: bool,
+    batch_text: String,
+    batch_file_path: String,
+    batch_format: InputFormat,
+    batch_salt_lines: bool,
+    batch_separator: String,
+    batch_use_formula: bool,
+    batch_algo: HashAlgorithm,
+    batch_params: HashParams,
+    batch_output_path: String,
+    batch_job: Option<BatchJob>,
+    batch_rows: Vec<BatchRow>,
+    batch_overflow: usize,
+    batch_progress: (u64, u64),
+    batch_recipe: String,
+    batch_mode: Option<u32>,
+    batch_encoding: String,
+    batch_uses_salt: bool,
+    batch_status: Option<Result<String, String>>,
+    // potfile 索引与已知哈希查询
+    pot_index: PotIndex,
+    pot_path: String,
+    pot_loading: Option<JoinHandle<Result<PotIndex, String>>>,
+    pot_status: Option<Result<String, String>>,
+    lookup_targets: String,
+    lookup_tables: Vec<LookupTable>,
+    table_path: String,
+    table_wordlist: String,
+    table_recipe: TableRecipe,
+    table_job: Option<TableJob>,
+    table_progress: (&'static str, u64, u64),
+    table_status: Option<Result<String, String>>,
+    // 查找表查询结果缓存 (目标 -> [(来源, 明文)])，键为查询时的目标文本
+    table_hits: HashMap<String, Vec<(String, String)>>,
+    table_hits_key: Option<String>,
+    // 以太坊工具
+    eth_signature: String,
+    eth_address: String,
     // 自定义加密块
     custom_blocks: Vec<CustomBlock>,
+    block_history: BlockHistory,
     literal_input: String,
     nested_algo_selection: HashAlgorithm,
-    dragging_source: Option<usize>,
+    // 导出代码
+    codegen_language: CodeLanguage,
+    codegen_output: Result<String, String>,
+    codegen_check: Result<usize, String>,
 }
 
 impl Default for HashApp {
@@ -135,10 +214,24 @@ impl Default for HashApp {
             active_tool: ActiveTool::Encryption,
             input_text: String::new(),
             salt_text: String::new(),
+            input_format: InputFormat::default(),
+            salt_format: InputFormat::default(),
+            input_bytes: Ok(Vec::new()),
+            salt_bytes: Ok(Vec::new()),
             // salt_mode: SaltMode::None,
             // selected_algo: HashAlgorithm::Md5,
             output_text: String::new(),
+            output_is_binary: false,
+            custom_trace: Vec::new(),
+            evaluator: Evaluator::default(),
+            checked_blocks: None,
             bulk_results: Vec::new(),
+            xof_params: HashParams::default(),
+            checksum_results: Vec::new(),
+            checksum_params: HashParams::default(),
+            display_format: DisplayFormat::LowerHex,
+            row_formats: HashMap::new(),
+            export_settings: ExportSettings::default(),
             search_query: String::new(),
             compare_target: String::new(),
             auto_calculate: true,
@@ -146,48 +239,146 @@ impl Default for HashApp {
             inference_target_hash: String::new(),
             inference_salt: String::new(),
             inference_results: Vec::new(),
+            inference_sort: InferenceSort::Score,
+            inference_grouped: true,
+            inference_filter: String::new(),
+            inference_exact_only: false,
             inference_fuzzy: false,
             inference_brute_salt: false,
+            inference_charsets: false,
             inference_use_custom_dict: false,
             inference_custom_dict_path: String::new(),
+            verify_stored: String::new(),
+            verify_password: String::new(),
+            verify_result: None,
+            verify_running: None,
+            batch_use_file: false,
+            batch_text: String::new(),
+            batch_file_path: String::new(),
+            batch_format: InputFormat::default(),
+            batch_salt_lines: false,
+            batch_separator: ":".to_string(),
+            batch_use_formula: false,
+            batch_algo: HashAlgorithm::Md5,
+            batch_params: HashParams::for_algo(HashAlgorithm::Md5),
+            batch_output_path: String::new(),
+            batch_job: None,
+            batch_rows: Vec::new(),
+            batch_overflow: 0,
+            batch_progress: (0, 0),
+            batch_recipe: String::new(),
+            batch_mode: None,
+            batch_encoding: String::new(),
+            batch_uses_salt: false,
+            batch_status: None,
+            pot_index: PotIndex::default(),
+            pot_path: String::new(),
+            pot_loading: None,
+            pot_status: None,
+            lookup_targets: String::new(),
+            lookup_tables: Vec::new(),
+            table_path: String::new(),
+            table_wordlist: String::new(),
+            table_recipe: TableRecipe::Md5,
+            table_job: None,
+            table_progress: ("", 0, 0),
+            table_status: None,
+            table_hits: HashMap::new(),
+            table_hits_key: None,
+            eth_signature: "transfer(address,uint256)".to_string(),
+            eth_address: String::new(),
             custom_blocks: vec![CustomBlock::Password, CustomBlock::Salt],
+            block_history: BlockHistory::default(),
             literal_input: String::new(),
             nested_algo_selection: HashAlgorithm::Md5,
-            dragging_source: None,
+            codegen_language: CodeLanguage::Php,
+            codegen_output: Ok(String::new()),
+            codegen_check: Ok(0),
+        }
+    }
+}
+
+/// 拖动中的积木：积木区内已有的积木 (按路径定位) 或从工具栏拖出的新积木
+enum BlockDrag {
+    Existing(Vec<usize>),
+    Palette(CustomBlock),
+}
+
+struct DropTarget {
+    list: Vec<usize>,
+    index: usize,
+    y: f32,
+    x_range: egui::Rangef,
+}
+
+/// 单帧内的拖放状态，各层级积木列表共享。
+/// 嵌套列表在外层积木之前完成判定，因此总是最内层的落点优先。
+struct DropState {
+    dragging: bool,
+    source: Option<Vec<usize>>,
+    pointer: Option<egui::Pos2>,
+    target: Option<DropTarget>,
+}
+
+impl DropState {
+    fn new(ctx: &egui::Context) -> Self {
+        let payload = egui::DragAndDrop::payload::<BlockDrag>(ctx);
+        let source = match payload.as_deref() {
+            Some(BlockDrag::Existing(path)) => Some(path.clone()),
+            _ => None,
+        };
+        Self {
+            dragging: payload.is_some(),
+            source,
+            pointer: ctx.input(|i| i.pointer.hover_pos()),
+            target: None,
+        }
+    }
+
+    /// 拖动中且指针位于 `rect` 内、尚未确定落点时返回指针位置
+    fn hover_pointer(&self, rect: egui::Rect) -> Option<egui::Pos2> {
+        if !self.dragging || self.target.is_some() {
+            return None;
+        }
+        self.pointer.filter(|p| rect.contains(*p))
+    }
+
+    fn claim(&mut self, list: &[usize], index: usize, y: f32, x_range: egui::Rangef) {
+        // 不能把积木放进它自己的内部
+        if let Some(source) = &self.source {
+            if list.starts_with(source) {
+                return;
+            }
         }
+        self.target = Some(DropTarget { list: list.to_vec(), index, y, x_range });
     }
 }
 
 impl HashApp {
     fn render_blocks(
-        input_text: &str,
-        salt_text: &str,
         ui: &mut egui::Ui,
         blocks: &mut Vec<CustomBlock>,
+        path: &[usize],
+        drop: &mut DropState,
         changed: &mut bool,
-        list_id: egui::Id,
-    ) {
+    ) -> egui::Rect {
         let mut to_remove = None;
-        let mut swap_target = None;
-        
-        // 从内存中获取当前列表的拖拽状态
-        let mut dragging_source = ui.data_mut(|d| d.get_temp::<Option<usize>>(list_id)).unwrap_or(None);
 
-        ui.vertical(|ui| {
+        let list_rect = ui.vertical(|ui| {
             ui.spacing_mut().item_spacing.y = 4.0;
 
-            let len = blocks.len();
-            for i in 0..len {
-                let color = match &blocks[i] {
+            for (i, block) in blocks.iter_mut().enumerate() {
+                let color = match block {
                     CustomBlock::Password => egui::Color32::from_rgb(76, 151, 255), // Scratch 蓝色
                     CustomBlock::Salt => egui::Color32::from_rgb(255, 171, 25),     // Scratch 橙色
                     CustomBlock::Literal(_) => egui::Color32::from_rgb(89, 192, 89), // Scratch 绿色
-                    CustomBlock::Hash(_, _) => egui::Color32::from_rgb(153, 102, 255), // Scratch 紫色
+                    CustomBlock::Hash { .. } => egui::Color32::from_rgb(153, 102, 255), // Scratch 紫色
+                    CustomBlock::Repeat { .. } => egui::Color32::from_rgb(255, 102, 128), // Scratch 粉色
                 };
+                let block_path: Vec<usize> = path.iter().copied().chain([i]).collect();
 
-                ui.horizontal(|ui| {
+                let row = ui.horizontal(|ui| {
                     // 1. 拖动手柄 (独立感应区)
-                    let _handle_id = ui.make_persistent_id(("handle", i, blocks.as_ptr()));
                     let (rect, response) = ui.allocate_at_least(egui::vec2(24.0, 30.0), egui::Sense::drag());
                     
                     // 绘制手柄视觉
@@ -195,142 +386,42 @@ impl HashApp {
                     ui.painter().rect_filled(rect.shrink(2.0), 4.0, visuals.bg_fill);
                     ui.painter().text(rect.center(), egui::Align2::CENTER_CENTER, "☰", egui::FontId::proportional(16.0), egui::Color32::WHITE);
 
-                    // 动态拖拽逻辑
-                    if response.drag_started() {
-                        dragging_source = Some(i);
-                    }
-                    if response.dragged() {
-                        if let Some(_source_idx) = dragging_source {
-                            // 检查鼠标当前位置是否在其他积木的区域内
-                            if let Some(_pointer_pos) = ui.input(|i| i.pointer.hover_pos()) {
-                                // 占位逻辑，实际交换在下方
-                            }
-                        }
-                    }
-                    if response.drag_stopped() {
-                        dragging_source = None;
-                    }
-                    
-                    // 更简单的实时交换逻辑：
-                    // 如果正在拖拽某个积木，我们检查鼠标位置相对于当前积木的位置
-                    if let Some(source_idx) = dragging_source {
-                        if source_idx != i {
-                            if let Some(pointer_pos) = ui.input(|i| i.pointer.hover_pos()) {
-                                // 如果鼠标悬停在当前积木的手柄区域，就交换
-                                if rect.contains(pointer_pos) {
-                                    swap_target = Some((source_idx, i));
-                                }
-                            }
-                        }
-                    }
+                    // 拖动手柄即携带该积木的路径，可放到任意层级
+                    response.dnd_set_drag_payload(BlockDrag::Existing(block_path.clone()));
 
                     // 2. 积木主体
-                    let block = &mut blocks[i];
                     match block {
-                        CustomBlock::Hash(algo, inner) => {
-                            // C-Block 形状实现 (全包含)
-                            ui.vertical(|ui| {
-                                ui.spacing_mut().item_spacing.y = 0.0; // 关键修复：移除垂直间距，使三部分无缝连接
-
-                                // 顶部栏
-                                let top_frame = egui::Frame::none()
-                                    .fill(color)
-                                    .rounding(egui::Rounding { nw: 10.0, ne: 10.0, sw: 0.0, se: 0.0 })
-                                    .inner_margin(egui::Margin::symmetric(8.0, 6.0));
-                                
-                                top_frame.show(ui, |ui| {
-                                    ui.set_width(ui.available_width());
-                                    ui.horizontal(|ui| {
-                                        ui.label(egui::RichText::new(format!("计算 {}", algo.name())).color(egui::Color32::WHITE).strong());
-                                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                                            if ui.button("x").clicked() { to_remove = Some(i); }
-                                        });
-                                    });
-                                });
-
-                                // 中间内容区 (左侧脊柱 + 内部积木)
-                                ui.horizontal(|ui| {
-                                    ui.spacing_mut().item_spacing.x = 0.0;
-                                    
-                                    // 动态计算脊柱高度：先记录起始位置
-                                    let spine_start_pos = ui.cursor().min;
-                                    let spine_width = 16.0;
-                                    
-                                    // 预留脊柱空间
-                                    ui.add_space(spine_width);
-
-                                    // 内部容器
-                                    let content_response = ui.vertical(|ui| {
-                                        ui.add_space(4.0);
-                                        ui.indent(ui.make_persistent_id(("indent", i)), |ui| {
-                                            // 递归调用时，我们需要传递 dragging_source，但要注意索引问题
-                                            // 这里的 inner 是一个新的 Vec，所以索引是局部的，这简化了问题
-                                            // 但我们需要一个新的 dragging_source 状态给子列表吗？
-                                            // 实际上，为了简化，我们暂时只支持同级拖拽。
-                                            // 如果要支持跨层级拖拽，需要更复杂的状态管理。
-                                            // 这里我们传入一个临时的 None，意味着子列表内部可以拖拽，但不能跨层级
-                                            // 修复：使用持久化 ID 来存储嵌套列表的拖拽状态
-                                            let inner_list_id = ui.make_persistent_id(("nested_list", i, inner.as_ptr()));
-                                            Self::render_blocks(input_text, salt_text, ui, inner, changed, inner_list_id);
-                                            
-                                            ui.horizontal(|ui| {
-                                                ui.style_mut().spacing.button_padding = egui::vec2(4.0, 2.0);
-                                                if ui.button("+P").on_hover_text("添加 Password").clicked() { inner.push(CustomBlock::Password); *changed = true; }
-                                                if ui.button("+S").on_hover_text("添加 Salt").clicked() { inner.push(CustomBlock::Salt); *changed = true; }
-                                                
-                                                // 新增：添加固定文本 (+T)
-                                                ui.menu_button("+T", |ui| {
-                                                    ui.set_min_width(150.0);
-                                                    let unique_id = ui.make_persistent_id(("popup_text_input", i, inner.as_ptr()));
-                                                    let mut text: String = ui.data(|d| d.get_temp(unique_id).unwrap_or_default());
-                                                    
-                                                    ui.label("输入固定文本:");
-                                                    let res = ui.text_edit_singleline(&mut text);
-                                                    
-                                                    if ui.button("确认添加").clicked() || (res.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter))) {
-                                                         if !text.is_empty() {
-                                                             inner.push(CustomBlock::Literal(text.clone()));
-                                                             *changed = true;
-                                                             ui.data_mut(|d| d.insert_temp(unique_id, String::new())); // Clear
-                                                             ui.close_menu();
-                                                         }
-                                                    } else {
-                                                         ui.data_mut(|d| d.insert_temp(unique_id, text)); // Save
-                                                    }
-                                                }).response.on_hover_text("添加固定文本 (Literal)");
-
-                                                egui::ComboBox::new(ui.make_persistent_id(("inner_algo", i)), "")
-                                                    .selected_text("添加哈希")
-                                                    .show_ui(ui, |ui| {
-                                                        for algo in HashAlgorithm::all() {
-                                                            if ui.button(algo.name()).clicked() {
-                                                                inner.push(CustomBlock::Hash(*algo, vec![]));
-                                                                *changed = true;
-                                                                ui.close_menu();
-                                                            }
-                                                        }
-                                                    });
-                                            });
-                                        });
-                                        ui.add_space(4.0);
-                                    }).response;
-                                    
-                                    // 绘制脊柱 (高度跟随内容)
-                                    let spine_rect = egui::Rect::from_min_size(
-                                        spine_start_pos,
-                                        egui::vec2(spine_width, content_response.rect.height())
-                                    );
-                                    ui.painter().rect_filled(spine_rect, 0.0, color);
+                        CustomBlock::Hash { algo, params, encoding, inner } => {
+                            Self::render_c_block(ui, &block_path, color, inner, drop, changed, |ui, changed| {
+                                ui.label(egui::RichText::new(format!("计算 {}", algo.name())).color(egui::Color32::WHITE).strong());
+                                Self::params_editor(ui, *algo, params, changed);
+                                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                    if ui.button("x").clicked() { to_remove = Some(i); }
+                                    Self::encoding_combo(ui, ("block_encoding", &block_path), encoding, changed);
                                 });
-
-                                // 底部栏 (闭合 C-Block)
-                                let bottom_frame = egui::Frame::none()
-                                    .fill(color)
-                                    .rounding(egui::Rounding { nw: 0.0, ne: 0.0, sw: 10.0, se: 10.0 })
-                                    .inner_margin(egui::Margin::symmetric(8.0, 4.0));
-                                bottom_frame.show(ui, |ui| {
-                                    ui.set_width(ui.available_width());
-                                    ui.label(" "); // 占位高度
+                            });
+                        }
+                        CustomBlock::Repeat { algo, params, encoding, rounds, rejoin, inner } => {
+                            Self::render_c_block(ui, &block_path, color, inner, drop, changed, |ui, changed| {
+                                ui.label(egui::RichText::new(format!("重复 {}", algo.name())).color(egui::Color32::WHITE).strong());
+                                Self::params_editor(ui, *algo, params, changed);
+                                if ui.add(egui::DragValue::new(rounds).range(1..=1_000_000).prefix("× ")).changed() {
+                                    *changed = true;
+                                }
+                                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                    if ui.button("x").clicked() { to_remove = Some(i); }
+                                    Self::encoding_combo(ui, ("repeat_encoding", &block_path), encoding, changed);
+                                    egui::ComboBox::new(ui.make_persistent_id(("repeat_rejoin", &block_path)), "")
+                                        .selected_text(rejoin.tag())
+                                        .show_ui(ui, |ui| {
+                                            for r in Rejoin::all() {
+                                                if ui.selectable_value(rejoin, *r, r.name()).changed() {
+                                                    *changed = true;
+                                                }
+                                            }
+                                        })
+                                        .response
+                                        .on_hover_text("每轮重新拼接 (在上一轮结果前/后追加 $pass 或 $salt)");
                                 });
                             });
                         }
@@ -352,37 +443,481 @@ impl HashApp {
                             });
                         }
                     }
-                });
+                }).response;
+
+                // 正在被拖动的积木半透明显示
+                if drop.source.as_deref() == Some(block_path.as_slice()) {
+                    ui.painter().rect_filled(row.rect, 6.0, egui::Color32::from_black_alpha(140));
+                }
+
+                // 落点判定：指针在上半部分插到此积木之前，下半部分插到之后。
+                // 嵌套列表在上面已先行判定，所以这里只会在没有更内层落点时生效。
+                if let Some(pointer) = drop.hover_pointer(row.rect) {
+                    if pointer.y < row.rect.center().y {
+                        drop.claim(path, i, row.rect.top() - 2.0, row.rect.x_range());
+                    } else {
+                        drop.claim(path, i + 1, row.rect.bottom() + 2.0, row.rect.x_range());
+                    }
+                }
             }
-        });
+
+            // 空列表在拖动时显示一个占位落点
+            if blocks.is_empty() && drop.dragging {
+                let (rect, _) = ui.allocate_exact_size(egui::vec2(ui.available_width().max(80.0), 24.0), egui::Sense::hover());
+                ui.painter().rect_stroke(rect, 4.0, egui::Stroke::new(1.0, egui::Color32::GRAY));
+                ui.painter().text(rect.center(), egui::Align2::CENTER_CENTER, "拖到这里", egui::FontId::proportional(12.0), egui::Color32::GRAY);
+                if drop.hover_pointer(rect).is_some() {
+                    drop.claim(path, 0, rect.center().y, rect.x_range());
+                }
+            }
+        }).response.rect;
 
         if let Some(i) = to_remove {
             blocks.remove(i);
             *changed = true;
         }
-        if let Some((from, to)) = swap_target {
-            blocks.swap(from, to);
-            dragging_source = Some(to); // 更新拖拽源索引，因为位置变了
+        list_rect
+    }
+
+    /// 工具栏按钮：点击追加到末尾，也可直接拖入积木区任意位置
+    fn palette_button(ui: &mut egui::Ui, label: &str, block: CustomBlock) -> egui::Response {
+        let response = ui
+            .add(egui::Button::new(label).sense(egui::Sense::click_and_drag()))
+            .on_hover_text("点击添加到末尾，或拖入积木区任意位置");
+        response.dnd_set_drag_payload(BlockDrag::Palette(block));
+        response
+    }
+
+    /// 绘制落点指示线、拖动预览，并在松开鼠标时执行移动/插入
+    fn finish_block_drop(&mut self, ui: &mut egui::Ui, drop: DropState, changed: &mut bool) {
+        if !drop.dragging {
+            return;
+        }
+        let ctx = ui.ctx().clone();
+        let Some(payload) = egui::DragAndDrop::payload::<BlockDrag>(&ctx) else { return };
+
+        if let Some(pointer) = drop.pointer {
+            let name = match payload.as_ref() {
+                BlockDrag::Existing(path) => blocks::block_at(&self.custom_blocks, path).map(|b| b.name()),
+                BlockDrag::Palette(block) => Some(block.name()),
+            };
+            if let Some(name) = name {
+                egui::Area::new(egui::Id::new("block_drag_preview"))
+                    .order(egui::Order::Tooltip)
+                    .fixed_pos(pointer + egui::vec2(14.0, 10.0))
+                    .interactable(false)
+                    .show(&ctx, |ui| {
+                        egui::Frame::popup(ui.style()).show(ui, |ui| ui.label(name));
+                    });
+            }
+        }
+
+        let Some(target) = drop.target else { return };
+        let painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("block_drop_line")));
+        painter.hline(target.x_range, target.y, egui::Stroke::new(3.0, egui::Color32::from_rgb(120, 180, 255)));
+
+        if ui.input(|i| i.pointer.any_released()) {
+            egui::DragAndDrop::clear_payload(&ctx);
+            let moved = match payload.as_ref() {
+                BlockDrag::Existing(from) => blocks::move_block(&mut self.custom_blocks, from, &target.list, target.index),
+                BlockDrag::Palette(block) => blocks::insert_block(&mut self.custom_blocks, &target.list, target.index, block.clone()),
+            };
+            if moved {
+                *changed = true;
+            }
+        }
+    }
+
+    fn encoding_combo(ui: &mut egui::Ui, id_source: impl std::hash::Hash, encoding: &mut OutputEncoding, changed: &mut bool) {
+        // 输出编码：决定结果以何种形式拼接到外层
+        egui::ComboBox::new(ui.make_persistent_id(id_source), "")
+            .selected_text(encoding.tag())
+            .show_ui(ui, |ui| {
+                for enc in OutputEncoding::all() {
+                    if ui.selectable_value(encoding, *enc, enc.name()).changed() {
+                        *changed = true;
+                    }
+                }
+            })
+            .response
+            .on_hover_text("输出编码 (嵌套时传给外层的形式)");
+    }
+
+    /// 输入框的编码选择与转义开关
+    fn input_format_editor(ui: &mut egui::Ui, id_source: impl std::hash::Hash, format: &mut InputFormat, changed: &mut bool) {
+        egui::ComboBox::new(ui.make_persistent_id(id_source), "")
+            .selected_text(format.encoding.name())
+            .show_ui(ui, |ui| {
+                for enc in InputEncoding::all() {
+                    if ui.selectable_value(&mut format.encoding, *enc, enc.name()).changed() {
+                        *changed = true;
+                    }
+                }
+            })
+            .response
+            .on_hover_text("输入内容按此编码转换为字节后参与计算");
+        if format.encoding.is_text()
+            && ui.checkbox(&mut format.escapes, "转义").on_hover_text("解析 \\n \\r \\t \\0 \\\\ \\u{XXXX}，\\xNN 为原始字节").changed()
+        {
             *changed = true;
         }
-        
-        // 将更新后的拖拽状态存回内存
-        ui.data_mut(|d| d.insert_temp(list_id, dragging_source));
     }
 
-    fn get_block_formula(&self, blocks: &[CustomBlock]) -> String {
-        let mut parts = Vec::new();
-        for block in blocks {
-            match block {
-                CustomBlock::Password => parts.push("$pass".to_string()),
-                CustomBlock::Salt => parts.push("$salt".to_string()),
-                CustomBlock::Literal(l) => parts.push(format!("\"{}\"", l)),
-                CustomBlock::Hash(algo, inner) => {
-                    parts.push(format!("{}({})", algo.name(), self.get_block_formula(inner)));
+    /// 导出菜单：选择格式，填写路径则保存到文件，否则复制到剪贴板。`records` 只在点击时构造
+    fn export_menu(ui: &mut egui::Ui, settings: &mut ExportSettings, records: impl FnOnce() -> Vec<ExportRecord>) {
+        ui.menu_button("📤 导出", |ui| {
+            ui.set_min_width(260.0);
+            for format in ExportFormat::all() {
+                ui.radio_value(&mut settings.format, *format, format.name());
+            }
+            ui.add(
+                egui::TextEdit::singleline(&mut settings.path)
+                    .hint_text(format!("保存路径 (.{})，留空则复制", settings.format.extension()))
+                    .desired_width(f32::INFINITY),
+            );
+            let to_file = !settings.path.trim().is_empty();
+            if ui.button(if to_file { "💾 保存到文件" } else { "📋 复制到剪贴板" }).clicked() {
+                let records = records();
+                let text = export::render(settings.format, &records);
+                settings.status = Some(if to_file {
+                    std::fs::write(settings.path.trim(), text)
+                        .map(|_| format!("已导出 {} 条到 {}", records.len(), settings.path.trim()))
+                        .map_err(|e| format!("导出失败: {}", e))
+                } else {
+                    ui.output_mut(|o| o.copied_text = text);
+                    Ok(format!("已复制 {} 条", records.len()))
+                });
+                ui.close_menu();
+            }
+        });
+        match &settings.status {
+            Some(Ok(msg)) => {
+                ui.small(msg);
+            }
+            Some(Err(e)) => {
+                ui.small(egui::RichText::new(e).color(egui::Color32::RED));
+            }
+            None => {}
+        }
+    }
+
+    /// 算法参数：XOF / BLAKE2 的输出长度、cSHAKE 自定义串、校验和种子，以及 BLAKE2/BLAKE3/SipHash 的密钥等 (收在 ⚙ 菜单中)
+    fn params_editor(ui: &mut egui::Ui, algo: HashAlgorithm, params: &mut HashParams, changed: &mut bool) {
+        if let Some(max) = algo.max_output_len() {
+            let len = ui
+                .add(egui::DragValue::new(&mut params.output_len).range(1..=max).suffix(" 字节"))
+                .on_hover_text("输出长度");
+            if len.changed() {
+                *changed = true;
+            }
+        }
+        if algo.has_customization() {
+            let custom = ui
+                .add(egui::TextEdit::singleline(&mut params.customization).hint_text("自定义串").desired_width(80.0))
+                .on_hover_text("customization string (S)");
+            if custom.changed() {
+                *changed = true;
+            }
+        }
+        if let Some(max) = algo.max_seed() {
+            let seed = ui
+                .add(egui::DragValue::new(&mut params.seed).range(0..=max).prefix("seed "))
+                .on_hover_text("种子 (seed)");
+            if seed.changed() {
+                *changed = true;
+            }
+        }
+        if algo.is_blake2() || algo.is_blake3() || algo == HashAlgorithm::SipHash24 {
+            ui.menu_button("⚙", |ui| {
+                let mut fields = vec![("密钥 (key)", &mut params.key)];
+                if algo.is_blake2() {
+                    fields.push(("salt", &mut params.salt));
+                    fields.push(("personalization", &mut params.personalization));
+                } else if algo.is_blake3() {
+                    fields.push(("derive_key context", &mut params.context));
                 }
+                egui::Grid::new("hash_params").num_columns(2).show(ui, |ui| {
+                    for (label, value) in fields {
+                        ui.label(label);
+                        if ui.add(egui::TextEdit::singleline(value).desired_width(160.0)).changed() {
+                            *changed = true;
+                        }
+                        ui.end_row();
+                    }
+                });
+                if algo.is_blake3() {
+                    ui.small("密钥需恰好 32 字节；填写 context 时使用 derive_key 模式");
+                } else if algo == HashAlgorithm::SipHash24 {
+                    ui.small("密钥需恰好 16 字节，留空为全零密钥");
+                }
+            })
+            .response
+            .on_hover_text("密钥与其他参数 (UTF-8 文本)");
+        }
+        if let Err(e) = params.validate(algo) {
+            ui.label(egui::RichText::new("⚠").color(egui::Color32::YELLOW)).on_hover_text(e);
+        }
+    }
+
+    /// 批量结果列表：按搜索词过滤，并高亮与对比目标一致的行。
+    /// 每行可单独指定显示格式 (`row_formats`，按标签保存)，未指定时使用全局格式。
+    fn result_rows(
+        ui: &mut egui::Ui,
+        rows: &[(String, String)],
+        query: &str,
+        target: &str,
+        format: DisplayFormat,
+        row_formats: &mut HashMap<String, DisplayFormat>,
+    ) {
+        let query = query.to_lowercase();
+        let target = target.trim();
+        // 目标按 hex / base64 等解码后与结果字节比较，与两边的显示格式无关
+        let target_bytes = crypto::decode_target(target);
+
+        for (label, hash) in rows {
+            let row_format = row_formats.get(label).copied().unwrap_or(format);
+            let shown = row_format.reformat(label, hash);
+
+            // 搜索过滤逻辑
+            if !query.is_empty() && !label.to_lowercase().contains(&query) && !shown.to_lowercase().contains(&query) {
+                continue;
+            }
+
+            // 对比匹配逻辑：摘要行按字节比较 (含部分匹配)，文本行按原文区分大小写完全比较
+            let match_kind = if target.is_empty() {
+                None
+            } else if let Some(bytes) = crypto::result_bytes(label, hash) {
+                crypto::compare_bytes(&bytes, &target_bytes)
+            } else if crypto::is_text_row(label) {
+                (hash == target).then_some(MatchKind::Exact)
+            } else {
+                row_format.matches(&shown, target).then_some(MatchKind::Exact)
+            };
+            let is_match = match_kind == Some(MatchKind::Exact);
+            let is_partial = match_kind.is_some() && !is_match;
+
+            let bg_color = if is_match {
+                egui::Color32::from_rgb(50, 100, 50) // 匹配成功显示深绿色背景
+            } else if is_partial {
+                egui::Color32::from_rgb(100, 80, 30) // 部分匹配显示暗黄色背景
+            } else {
+                egui::Color32::TRANSPARENT
+            };
+
+            egui::Frame::none().fill(bg_color).inner_margin(2.0).show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.spacing_mut().item_spacing.x = 10.0;
+                    // 固定宽度的标签列
+                    ui.scope(|ui| {
+                        ui.set_min_width(150.0);
+                        ui.set_max_width(150.0);
+                        let text = if is_match {
+                            egui::RichText::new(format!("✅ {}", label)).color(egui::Color32::GREEN).strong()
+                        } else if is_partial {
+                            egui::RichText::new(format!("◐ {}", label)).color(egui::Color32::YELLOW).strong()
+                        } else {
+                            egui::RichText::new(label).strong()
+                        };
+                        let response = ui.label(text);
+                        if let Some(kind) = match_kind {
+                            response.on_hover_text(kind.name());
+                        }
+                    });
+
+                    // 右侧：单行格式与复制按钮，文本框占满剩余空间
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.small_button("📋").on_hover_text("复制").clicked() {
+                            ui.output_mut(|o| o.copied_text = shown.clone());
+                        }
+                        let tag = if row_formats.contains_key(label) {
+                            egui::RichText::new(row_format.tag()).strong()
+                        } else {
+                            egui::RichText::new(row_format.tag()).weak()
+                        };
+                        ui.menu_button(tag, |ui| {
+                            if ui.button("跟随全局").clicked() {
+                                row_formats.remove(label);
+                                ui.close_menu();
+                            }
+                            ui.separator();
+                            for candidate in DisplayFormat::all() {
+                                if ui.button(candidate.name()).clicked() {
+                                    row_formats.insert(label.clone(), *candidate);
+                                    ui.close_menu();
+                                }
+                            }
+                        })
+                        .response
+                        .on_hover_text("本行显示格式");
+
+                        let mut hash_text = shown.clone();
+                        let text_edit = egui::TextEdit::singleline(&mut hash_text)
+                            .desired_width(f32::INFINITY);
+
+                        let response = ui.add(text_edit);
+                        if is_match {
+                            response.highlight(); // 高亮文本框边框
+                        }
+                    });
+                });
+            });
+        }
+    }
+
+    /// 以 hex 和文本两种形式显示一段中间字节
+    fn trace_bytes(ui: &mut egui::Ui, caption: &str, bytes: &[u8]) {
+        ui.horizontal_wrapped(|ui| {
+            ui.label(format!("{} ({} 字节):", caption, bytes.len()));
+            ui.monospace(hex::encode(bytes));
+        });
+        ui.horizontal_wrapped(|ui| {
+            ui.add_space(12.0);
+            match std::str::from_utf8(bytes) {
+                Ok(text) => ui.monospace(format!("{:?}", text)),
+                Err(_) => ui.weak("(非 UTF-8 文本)"),
+            };
+        });
+    }
+
+    /// 计算过程树：每个节点显示送入哈希的输入与该积木的输出
+    fn render_trace(ui: &mut egui::Ui, nodes: &[TraceNode], path: &[usize]) {
+        for (i, node) in nodes.iter().enumerate() {
+            let mut node_path = path.to_vec();
+            node_path.push(i);
+            if node.input.is_none() && node.children.is_empty() {
+                Self::trace_bytes(ui, &node.label, &node.output);
+                continue;
             }
+            egui::CollapsingHeader::new(egui::RichText::new(&node.label).monospace())
+                .id_salt(("trace", &node_path))
+                .show(ui, |ui| {
+                    Self::render_trace(ui, &node.children, &node_path);
+                    if let Some(input) = &node.input {
+                        Self::trace_bytes(ui, "输入", input);
+                    }
+                    Self::trace_bytes(ui, "输出", &node.output);
+                });
         }
-        parts.join(" + ")
+    }
+
+    /// C 形积木：顶部栏 + 左侧脊柱包裹的子积木列表 + 底部栏
+    fn render_c_block(
+        ui: &mut egui::Ui,
+        path: &[usize],
+        color: egui::Color32,
+        inner: &mut Vec<CustomBlock>,
+        drop: &mut DropState,
+        changed: &mut bool,
+        header: impl FnOnce(&mut egui::Ui, &mut bool),
+    ) {
+        // C-Block 形状实现 (全包含)
+        ui.vertical(|ui| {
+            ui.spacing_mut().item_spacing.y = 0.0; // 关键修复：移除垂直间距，使三部分无缝连接
+
+            // 顶部栏
+            let top_frame = egui::Frame::none()
+                .fill(color)
+                .rounding(egui::Rounding { nw: 10.0, ne: 10.0, sw: 0.0, se: 0.0 })
+                .inner_margin(egui::Margin::symmetric(8.0, 6.0));
+            
+            top_frame.show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                ui.horizontal(|ui| header(ui, changed));
+            });
+
+            // 中间内容区 (左侧脊柱 + 内部积木)
+            ui.horizontal(|ui| {
+                ui.spacing_mut().item_spacing.x = 0.0;
+                
+                // 动态计算脊柱高度：先记录起始位置
+                let spine_start_pos = ui.cursor().min;
+                let spine_width = 16.0;
+                
+                // 预留脊柱空间
+                ui.add_space(spine_width);
+
+                // 内部容器
+                let content_response = ui.vertical(|ui| {
+                    ui.add_space(4.0);
+                    ui.indent(ui.make_persistent_id(("indent", path)), |ui| {
+                        // 子列表的路径即本积木的路径，拖放状态在各层级间共享，支持跨层级拖拽
+                        let list_rect = Self::render_blocks(ui, inner, path, drop, changed);
+                        
+                        ui.horizontal(|ui| {
+                            ui.style_mut().spacing.button_padding = egui::vec2(4.0, 2.0);
+                            if ui.button("+P").on_hover_text("添加 Password").clicked() { inner.push(CustomBlock::Password); *changed = true; }
+                            if ui.button("+S").on_hover_text("添加 Salt").clicked() { inner.push(CustomBlock::Salt); *changed = true; }
+                            
+                            // 新增：添加固定文本 (+T)
+                            ui.menu_button("+T", |ui| {
+                                ui.set_min_width(150.0);
+                                let unique_id = ui.make_persistent_id(("popup_text_input", path));
+                                let mut text: String = ui.data(|d| d.get_temp(unique_id).unwrap_or_default());
+                                
+                                ui.label("输入固定文本:");
+                                let res = ui.text_edit_singleline(&mut text);
+                                
+                                if ui.button("确认添加").clicked() || (res.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter))) {
+                                     if !text.is_empty() {
+                                         inner.push(CustomBlock::Literal(text.clone()));
+                                         *changed = true;
+                                         ui.data_mut(|d| d.insert_temp(unique_id, String::new())); // Clear
+                                         ui.close_menu();
+                                     }
+                                } else {
+                                     ui.data_mut(|d| d.insert_temp(unique_id, text)); // Save
+                                }
+                            }).response.on_hover_text("添加固定文本 (Literal)");
+
+                            egui::ComboBox::new(ui.make_persistent_id(("inner_algo", path)), "")
+                                .selected_text("添加哈希")
+                                .show_ui(ui, |ui| {
+                                    for algo in HashAlgorithm::all() {
+                                        if ui.button(algo.name()).clicked() {
+                                            inner.push(CustomBlock::hash(*algo));
+                                            *changed = true;
+                                            ui.close_menu();
+                                        }
+                                    }
+                                });
+
+                            ui.menu_button("+L", |ui| {
+                                for algo in HashAlgorithm::all() {
+                                    if ui.button(algo.name()).clicked() {
+                                        inner.push(CustomBlock::repeat(*algo));
+                                        *changed = true;
+                                        ui.close_menu();
+                                    }
+                                }
+                            }).response.on_hover_text("添加循环块 (多轮哈希)");
+                        });
+
+                        // 指针在内部空白处 (如添加按钮一行) 时追加到子列表末尾
+                        if drop.hover_pointer(ui.min_rect()).is_some() {
+                            drop.claim(path, inner.len(), list_rect.bottom() + 2.0, list_rect.x_range());
+                        }
+                    });
+                    ui.add_space(4.0);
+                }).response;
+                
+                // 绘制脊柱 (高度跟随内容)
+                let spine_rect = egui::Rect::from_min_size(
+                    spine_start_pos,
+                    egui::vec2(spine_width, content_response.rect.height())
+                );
+                ui.painter().rect_filled(spine_rect, 0.0, color);
+            });
+
+            // 底部栏 (闭合 C-Block)
+            let bottom_frame = egui::Frame::none()
+                .fill(color)
+                .rounding(egui::Rounding { nw: 0.0, ne: 0.0, sw: 10.0, se: 10.0 })
+                .inner_margin(egui::Margin::symmetric(8.0, 4.0));
+            bottom_frame.show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                ui.label(" "); // 占位高度
+            });
+        });
     }
 
     fn ui_encryption(&mut self, ui: &mut egui::Ui) {
@@ -395,7 +930,10 @@ impl HashApp {
 
         // 1. 公共输入区域 (Top)
         ui.group(|ui| {
-            ui.label("输入内容 (Password):");
+            ui.horizontal(|ui| {
+                ui.label("输入内容 (Password):");
+                Self::input_format_editor(ui, "input_format", &mut self.input_format, &mut changed);
+            });
             let response = ui.add(
                 egui::TextEdit::multiline(&mut self.input_text)
                     .hint_text("在此输入要计算哈希的文本...")
@@ -412,7 +950,13 @@ impl HashApp {
                 if ui.text_edit_singleline(&mut self.salt_text).changed() {
                     changed = true;
                 }
+                Self::input_format_editor(ui, "salt_format", &mut self.salt_format, &mut changed);
             });
+            for (caption, bytes) in [("输入内容", &self.input_bytes), ("盐值", &self.salt_bytes)] {
+                if let Err(e) = bytes {
+                    ui.label(egui::RichText::new(format!("❌ {}: {}", caption, e)).color(egui::Color32::RED));
+                }
+            }
         });
 
         ui.add_space(10.0);
@@ -426,6 +970,19 @@ impl HashApp {
                         self.search_query.clear();
                         self.compare_target.clear();
                     }
+                    let encoding = if self.input_format == self.salt_format {
+                        self.input_format.describe()
+                    } else {
+                        format!("pass={}; salt={}", self.input_format.describe(), self.salt_format.describe())
+                    };
+                    let plain = self.input_bytes.as_deref().unwrap_or_default();
+                    Self::export_menu(ui, &mut self.export_settings, || {
+                        self.bulk_results
+                            .iter()
+                            .chain(&self.checksum_results)
+                            .map(|(label, hash)| ExportRecord::from_label(label, hash, &self.salt_text, plain, &encoding))
+                            .collect()
+                    });
                 });
             });
             ui.separator();
@@ -438,7 +995,32 @@ impl HashApp {
                 ui.add_space(10.0);
                 
                 ui.label("⚖️ 对比:");
-                ui.add(egui::TextEdit::singleline(&mut self.compare_target).hint_text("输入目标哈希进行匹配...").desired_width(150.0));
+                ui.add(egui::TextEdit::singleline(&mut self.compare_target).hint_text("输入目标哈希进行匹配...").desired_width(150.0))
+                    .on_hover_text("支持 hex (可带 0x 前缀或冒号分隔)、Base64、Base64URL；截断或中间片段显示为部分匹配");
+
+                ui.add_space(10.0);
+
+                ui.label("🔤 显示格式:").on_hover_text("批量结果与自定义结果 (非 UTF-8 时) 的显示、复制和对比格式");
+                egui::ComboBox::new("display_format", "")
+                    .selected_text(self.display_format.name())
+                    .show_ui(ui, |ui| {
+                        for format in DisplayFormat::all() {
+                            if ui.selectable_value(&mut self.display_format, *format, format.name()).changed() {
+                                changed = true;
+                            }
+                        }
+                    });
+            });
+            ui.horizontal(|ui| {
+                ui.label("📏 XOF 输出长度:")
+                    .on_hover_text("SHAKE / cSHAKE / KangarooTwelve / BLAKE3 XOF 的输出字节数");
+                if ui.add(egui::DragValue::new(&mut self.xof_params.output_len).range(1..=XOF_MAX_LEN).suffix(" 字节")).changed() {
+                    changed = true;
+                }
+                ui.label("自定义串:").on_hover_text("cSHAKE / KangarooTwelve 的 customization string");
+                if ui.add(egui::TextEdit::singleline(&mut self.xof_params.customization).desired_width(120.0)).changed() {
+                    changed = true;
+                }
             });
             ui.add_space(5.0);
             
@@ -447,51 +1029,34 @@ impl HashApp {
                 .show(ui, |ui| {
                     ui.set_min_width(ui.available_width()); // 强制内容区域占满宽度
                     
-                    let query = self.search_query.to_lowercase();
-                    let target = self.compare_target.trim();
+                    Self::result_rows(ui, &self.bulk_results, &self.search_query, &self.compare_target, self.display_format, &mut self.row_formats);
+                });
 
-                    for (label, hash) in &self.bulk_results {
-                        // 搜索过滤逻辑
-                        if !query.is_empty() && !label.to_lowercase().contains(&query) && !hash.to_lowercase().contains(&query) {
-                            continue;
+            ui.add_space(5.0);
+            egui::CollapsingHeader::new("校验和 (非加密)")
+                .id_salt("checksum_results")
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("🌱 种子:").on_hover_text("xxHash / XXH3 / MurmurHash3 的种子 (seed)；xxh32 与 murmur3 为 32 位，xxh64 与 XXH3 为 64 位");
+                        // 各行共用一个种子，取 64 位算法的上限；超出 32 位的种子只省略 xxh32 / murmur3 行
+                        let max_seed = HashAlgorithm::all().iter().filter_map(|algo| algo.max_seed()).max().unwrap_or(0);
+                        let seed = egui::DragValue::new(&mut self.checksum_params.seed).range(0..=max_seed);
+                        if ui.add(seed).changed() {
+                            changed = true;
                         }
-
-                        // 对比匹配逻辑
-                        let is_match = !target.is_empty() && hash.eq_ignore_ascii_case(target);
-                        
-                        let bg_color = if is_match {
-                            egui::Color32::from_rgb(50, 100, 50) // 匹配成功显示深绿色背景
-                        } else {
-                            egui::Color32::TRANSPARENT
-                        };
-
-                        egui::Frame::none().fill(bg_color).inner_margin(2.0).show(ui, |ui| {
-                            ui.horizontal(|ui| {
-                                ui.spacing_mut().item_spacing.x = 10.0;
-                                // 固定宽度的标签列
-                                ui.scope(|ui| {
-                                    ui.set_min_width(150.0);
-                                    ui.set_max_width(150.0);
-                                    let text = if is_match {
-                                        egui::RichText::new(format!("✅ {}", label)).color(egui::Color32::GREEN).strong()
-                                    } else {
-                                        egui::RichText::new(label).strong()
-                                    };
-                                    ui.label(text);
-                                });
-                                
-                                // 文本框占满剩余空间
-                                let mut hash_text = hash.clone();
-                                let text_edit = egui::TextEdit::singleline(&mut hash_text)
-                                    .desired_width(f32::INFINITY);
-                                
-                                let response = ui.add(text_edit);
-                                if is_match {
-                                    response.highlight(); // 高亮文本框边框
-                                }
-                            });
-                        });
-                    }
+                        ui.label("SipHash 密钥:").on_hover_text("16 字节，留空为全零密钥");
+                        if ui.add(egui::TextEdit::singleline(&mut self.checksum_params.key).desired_width(140.0)).changed() {
+                            changed = true;
+                        }
+                        let omitted = omitted_checksums(&self.checksum_params);
+                        if !omitted.is_empty() {
+                            let labels: Vec<&str> = omitted.iter().map(|(label, _)| *label).collect();
+                            let reasons: Vec<&str> = omitted.iter().map(|(_, e)| e.as_str()).collect();
+                            ui.label(egui::RichText::new(format!("⚠ 已省略 {} 行", labels.join(" / "))).color(egui::Color32::YELLOW))
+                                .on_hover_text(reasons.join("\n"));
+                        }
+                    });
+                    Self::result_rows(ui, &self.checksum_results, &self.search_query, &self.compare_target, self.display_format, &mut self.row_formats);
                 });
         });
 
@@ -501,13 +1066,43 @@ impl HashApp {
         ui.group(|ui| {
             ui.heading("自定义加密 (Custom Builder)");
             ui.separator();
-            
+
+            // 撤销/重做快捷键：仅在没有输入框获得焦点时响应，避免与文本框自带的撤销冲突
+            if ui.memory(|m| m.focused().is_none()) {
+                let (undo, redo) = ui.input_mut(|i| {
+                    let redo = i.consume_shortcut(&egui::KeyboardShortcut::new(egui::Modifiers::COMMAND | egui::Modifiers::SHIFT, egui::Key::Z))
+                        || i.consume_shortcut(&egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Y));
+                    let undo = i.consume_shortcut(&egui::KeyboardShortcut::new(egui::Modifiers::COMMAND, egui::Key::Z));
+                    (undo, redo)
+                });
+                if undo && self.block_history.undo(&mut self.custom_blocks) {
+                    changed = true;
+                }
+                if redo && self.block_history.redo(&mut self.custom_blocks) {
+                    changed = true;
+                }
+            }
+
+            ui.horizontal(|ui| {
+                if ui.add_enabled(self.block_history.can_undo(), egui::Button::new("↶ 撤销")).on_hover_text("Ctrl+Z").clicked() {
+                    self.block_history.undo(&mut self.custom_blocks);
+                    changed = true;
+                }
+                if ui.add_enabled(self.block_history.can_redo(), egui::Button::new("↷ 重做")).on_hover_text("Ctrl+Y / Ctrl+Shift+Z").clicked() {
+                    self.block_history.redo(&mut self.custom_blocks);
+                    changed = true;
+                }
+            });
+
+            // 记录本帧修改前的快照，所有积木修改 (含嵌套) 都在下方发生
+            let blocks_before = self.custom_blocks.clone();
+
             ui.horizontal(|ui| {
-                if ui.button("➕ Password").clicked() {
+                if Self::palette_button(ui, "➕ Password", CustomBlock::Password).clicked() {
                     self.custom_blocks.push(CustomBlock::Password);
                     changed = true;
                 }
-                if ui.button("➕ Salt").clicked() {
+                if Self::palette_button(ui, "➕ Salt", CustomBlock::Salt).clicked() {
                     self.custom_blocks.push(CustomBlock::Salt);
                     changed = true;
                 }
@@ -515,7 +1110,13 @@ impl HashApp {
                 ui.separator();
                 ui.label("固定文本:");
                 ui.text_edit_singleline(&mut self.literal_input);
-                if ui.button("➕ 添加").clicked() && !self.literal_input.is_empty() {
+                let literal = CustomBlock::Literal(self.literal_input.clone());
+                let literal_button = if self.literal_input.is_empty() {
+                    ui.button("➕ 添加")
+                } else {
+                    Self::palette_button(ui, "➕ 添加", literal)
+                };
+                if literal_button.clicked() && !self.literal_input.is_empty() {
                     self.custom_blocks.push(CustomBlock::Literal(self.literal_input.clone()));
                     self.literal_input.clear();
                     changed = true;
@@ -529,8 +1130,15 @@ impl HashApp {
                             ui.selectable_value(&mut self.nested_algo_selection, *algo, algo.name());
                         }
                     });
-                if ui.button("➕ 添加哈希块").clicked() {
-                    self.custom_blocks.push(CustomBlock::Hash(self.nested_algo_selection, vec![]));
+                if Self::palette_button(ui, "➕ 添加哈希块", CustomBlock::hash(self.nested_algo_selection)).clicked() {
+                    self.custom_blocks.push(CustomBlock::hash(self.nested_algo_selection));
+                    changed = true;
+                }
+                if Self::palette_button(ui, "➕ 添加循环块", CustomBlock::repeat(self.nested_algo_selection))
+                    .on_hover_text("对内部结果重复计算 N 轮，如 md5^1000($pass)")
+                    .clicked()
+                {
+                    self.custom_blocks.push(CustomBlock::repeat(self.nested_algo_selection));
                     changed = true;
                 }
 
@@ -549,32 +1157,138 @@ impl HashApp {
                 .inner_margin(10.0);
 
             ui.label("积木搭建区:");
+            let mut drop = DropState::new(ui.ctx());
             frame.show(ui, |ui| {
                 ui.set_min_height(150.0);
                 ui.set_width(ui.available_width());
                 
-                egui::ScrollArea::both().show(ui, |ui| {
-                    // 使用固定的 ID 作为根列表的 ID
-                    let root_list_id = ui.make_persistent_id("root_block_list");
-                    Self::render_blocks(&self.input_text, &self.salt_text, ui, &mut self.custom_blocks, &mut changed, root_list_id);
-                });
+                let list_rect = egui::ScrollArea::both().show(ui, |ui| {
+                    Self::render_blocks(ui, &mut self.custom_blocks, &[], &mut drop, &mut changed)
+                }).inner;
+
+                // 搭建区空白处：追加到最外层末尾
+                if drop.hover_pointer(ui.min_rect()).is_some() {
+                    drop.claim(&[], self.custom_blocks.len(), list_rect.bottom() + 2.0, list_rect.x_range());
+                }
+            });
+            self.finish_block_drop(ui, drop, &mut changed);
+
+            if self.custom_blocks != blocks_before {
+                let now = ui.input(|i| i.time);
+                self.block_history.record(blocks_before, &self.custom_blocks, now);
+            }
+
+            ui.collapsing("🕘 历史记录", |ui| {
+                let done: Vec<&str> = self.block_history.done_labels().collect();
+                let undone: Vec<&str> = self.block_history.undone_labels().collect();
+                if done.is_empty() && undone.is_empty() {
+                    ui.weak("暂无修改记录");
+                }
+                let mut undo_steps = 0;
+                let mut redo_steps = 0;
+                for (idx, label) in done.iter().enumerate() {
+                    if ui.selectable_label(false, format!("{}. {}", idx + 1, label)).on_hover_text("撤销到此步骤之前").clicked() {
+                        undo_steps = done.len() - idx;
+                    }
+                }
+                for (idx, label) in undone.iter().enumerate() {
+                    let text = egui::RichText::new(format!("{}. {}", done.len() + idx + 1, label)).weak();
+                    if ui.selectable_label(false, text).on_hover_text("重做到此步骤").clicked() {
+                        redo_steps = idx + 1;
+                    }
+                }
+                for _ in 0..undo_steps {
+                    self.block_history.undo(&mut self.custom_blocks);
+                    changed = true;
+                }
+                for _ in 0..redo_steps {
+                    self.block_history.redo(&mut self.custom_blocks);
+                    changed = true;
+                }
             });
 
             ui.add_space(5.0);
-            let formula = self.get_block_formula(&self.custom_blocks);
+            let formula = blocks::formula(&self.custom_blocks);
             ui.horizontal(|ui| {
                 ui.label("公式预览:");
                 ui.code(&formula);
             });
+
+            ui.collapsing("🔍 计算过程", |ui| {
+                if self.custom_trace.is_empty() {
+                    ui.weak("积木区为空");
+                }
+                Self::render_trace(ui, &self.custom_trace, &[]);
+            });
             
             ui.separator();
             ui.horizontal(|ui| {
                 ui.label("自定义结果:");
-                ui.add(
-                    egui::TextEdit::singleline(&mut self.output_text)
-                        .desired_width(f32::INFINITY)
-                );
-            });
+                if self.evaluator.is_busy() {
+                    ui.spinner().on_hover_text("计算中，结果更新前显示的是上一次的结果");
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.small_button("📋").on_hover_text("复制").clicked() {
+                        ui.output_mut(|o| o.copied_text = self.output_text.clone());
+                    }
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.output_text)
+                            .desired_width(f32::INFINITY)
+                    );
+                });
+            });
+            if self.output_is_binary {
+                ui.small(format!("结果包含非 UTF-8 字节，已以 {} 显示", self.display_format.name()));
+            }
+
+            ui.collapsing("📤 导出代码", |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("目标语言:");
+                    egui::ComboBox::new("codegen_language", "")
+                        .selected_text(self.codegen_language.name())
+                        .show_ui(ui, |ui| {
+                            for lang in CodeLanguage::all() {
+                                if ui.selectable_value(&mut self.codegen_language, *lang, lang.name()).changed() {
+                                    changed = true;
+                                }
+                            }
+                        });
+                    if let Ok(code) = &self.codegen_output {
+                        if ui.button("📋 复制代码").clicked() {
+                            ui.output_mut(|o| o.copied_text = code.clone());
+                        }
+                    }
+                });
+
+                match &self.codegen_check {
+                    Ok(samples) => ui.label(
+                        egui::RichText::new(format!("✅ 自检通过：{} 组样例输入下，代码生成所用的计算步骤与积木结果一致", samples))
+                            .color(egui::Color32::GREEN),
+                    )
+                    .on_hover_text("只比较步骤表与积木的计算结果，导出的代码本身未在此运行"),
+                    Err(e) => ui.label(egui::RichText::new(format!("❌ 自检失败：{}", e)).color(egui::Color32::RED)),
+                };
+                if self.input_format != InputFormat::default() || self.salt_format != InputFormat::default() {
+                    ui.label(
+                        egui::RichText::new("⚠ 导出代码中的口令/盐值按 UTF-8 文本原样写入，未应用输入编码与转义")
+                            .color(egui::Color32::YELLOW),
+                    );
+                }
+
+                match &self.codegen_output {
+                    Ok(code) => {
+                        let mut code = code.clone();
+                        ui.add(
+                            egui::TextEdit::multiline(&mut code)
+                                .code_editor()
+                                .desired_width(f32::INFINITY),
+                        );
+                    }
+                    Err(e) => {
+                        ui.label(egui::RichText::new(format!("❌ 无法导出：{}", e)).color(egui::Color32::RED));
+                    }
+                }
+            });
         });
 
         // Global Control
@@ -583,10 +1297,8 @@ impl HashApp {
             if ui.button("立即计算").clicked() {
                 self.calculate();
             }
-            if ui.checkbox(&mut self.auto_calculate, "实时计算").changed() {
-                if self.auto_calculate {
-                    changed = true;
-                }
+            if ui.checkbox(&mut self.auto_calculate, "实时计算").changed() && self.auto_calculate {
+                changed = true;
             }
         });
 
@@ -626,6 +1338,8 @@ impl HashApp {
                     ui.checkbox(&mut self.inference_fuzzy, "模糊匹配 (包含关系)");
                     ui.checkbox(&mut self.inference_brute_salt, "爆破常见盐值 (0-1000, admin...)");
                 });
+                ui.checkbox(&mut self.inference_charsets, "尝试多种字符集 (UTF-8 / GBK / UTF-16LE / Big5 / Latin-1)")
+                    .on_hover_text("中文口令匹配不上时，目标系统可能按 GBK 或 UTF-16 字节计算；明文与盐值使用同一字符集");
                 
                 ui.horizontal(|ui| {
                     ui.checkbox(&mut self.inference_use_custom_dict, "使用自定义字典 (txt)");
@@ -650,26 +1364,51 @@ impl HashApp {
                     }
                 }
 
+                if !self.pot_index.is_empty() {
+                    ui.small(format!("📚 已加载 potfile 索引 {} 条，分析前会先查询目标是否已破解", self.pot_index.len()));
+                }
+
                 ui.add_space(10.0);
                 if ui.button("🚀 开始碰撞分析").clicked() {
                     self.inference_results.clear();
+                    // 先查 potfile：已破解的目标直接给出明文，明文/盐值留空时自动填入以继续推算算法
+                    let known: Vec<PotEntry> = self.pot_index.lookup(&self.inference_target_hash);
+                    self.inference_results.extend(known.iter().cloned().map(InferenceResult::Known));
+                    if let Some(entry) = known.first() {
+                        if self.inference_plaintext.is_empty() {
+                            match String::from_utf8(entry.plain.clone()) {
+                                Ok(plain) => {
+                                    self.inference_plaintext = plain;
+                                    if let (true, Some(salt)) = (self.inference_salt.is_empty(), &entry.salt) {
+                                        self.inference_salt = salt.clone();
+                                    }
+                                }
+                                Err(_) => self.inference_results.push(InferenceResult::Info(
+                                    "ℹ potfile 中的明文不是 UTF-8 (可能为 GBK 等编码)，未自动填入".to_string(),
+                                )),
+                            }
+                        }
+                    }
                     if self.inference_plaintext.is_empty() || self.inference_target_hash.is_empty() {
-                        self.inference_results.push("❌ 请先输入明文和目标哈希值".to_string());
+                        self.inference_results.push(InferenceResult::Error("请先输入明文和目标哈希值".to_string()));
                     } else {
-                        let target = self.inference_target_hash.trim().to_lowercase();
-                        let mut found = false;
-                        
-                        // 准备盐值列表
-                        let mut salts_to_try = vec![self.inference_salt.clone()];
+                        // 十六进制结果不区分大小写，base64 / ldap / apr1 等文本行按原样比较
+                        let exact_target = self.inference_target_hash.trim().to_string();
+                        let target = exact_target.to_lowercase();
+
+                        // 准备盐值列表 (同一盐值保留最可信的来源)
+                        let user_source = if self.inference_salt.is_empty() { SaltSource::None } else { SaltSource::User };
+                        let mut salts_to_try = vec![(self.inference_salt.clone(), user_source)];
                         if self.inference_brute_salt {
                             // 添加常见盐值
                             let common_salts = ["", "123456", "password", "salt", "admin", "123", "1", "0", "test", "root"];
                             for s in common_salts {
-                                salts_to_try.push(s.to_string());
+                                let source = if s.is_empty() { SaltSource::None } else { SaltSource::Common };
+                                salts_to_try.push((s.to_string(), source));
                             }
                             // 添加数字盐值 0-1000
                             for i in 0..=1000 {
-                                salts_to_try.push(i.to_string());
+                                salts_to_try.push((i.to_string(), SaltSource::Numeric));
                             }
                         }
 
@@ -678,59 +1417,108 @@ impl HashApp {
                             match std::fs::read_to_string(&self.inference_custom_dict_path) {
                                 Ok(content) => {
                                     for line in content.lines() {
-                                        salts_to_try.push(line.trim().to_string());
+                                        salts_to_try.push((line.trim().to_string(), SaltSource::Dictionary));
                                     }
-                                    self.inference_results.push(format!("📂 已加载自定义字典: {}", self.inference_custom_dict_path));
+                                    self.inference_results.push(InferenceResult::Info(format!("📂 已加载自定义字典: {}", self.inference_custom_dict_path)));
                                 }
                                 Err(e) => {
-                                    self.inference_results.push(format!("❌ 无法读取字典文件: {}", e));
+                                    self.inference_results.push(InferenceResult::Error(format!("无法读取字典文件: {}", e)));
                                 }
                             }
                         }
 
                         // 去重
-                        salts_to_try.sort();
-                        salts_to_try.dedup();
+                        let mut seen = std::collections::HashSet::new();
+                        salts_to_try.retain(|(salt, _)| seen.insert(salt.clone()));
 
                         let total_salts = salts_to_try.len();
-                        let mut match_count = 0;
 
-                        for salt in salts_to_try {
-                            let candidates = calculate_complex_hashes(&self.inference_plaintext, &salt);
+                        // 字符集变体。纯 ASCII 明文在各字符集下字节相同，但盐值可能不同 (如中文盐)，
+                        // 所以这里保留全部变体，在计算时按 (明文, 盐) 字节去重
+                        let charsets: &[InputEncoding] =
+                            if self.inference_charsets { InputEncoding::charset_variants() } else { &[InputEncoding::Utf8] };
+                        let mut plain_variants: Vec<(InputEncoding, Vec<u8>)> = Vec::new();
+                        for charset in charsets {
+                            match charset.encode_text(&self.inference_plaintext) {
+                                Ok(bytes) => plain_variants.push((*charset, bytes)),
+                                Err(e) => self.inference_results.push(InferenceResult::Info(format!("ℹ 跳过 {}: 明文{}", charset.name(), e))),
+                            }
+                        }
+
+                        // 8 / 16 位十六进制目标可能是 32 / 64 位校验和，只对明文本身计算
+                        if (target.len() == 8 || target.len() == 16) && target.chars().all(|c| c.is_ascii_hexdigit()) {
+                            let mut seen_plain = std::collections::HashSet::new();
+                            for (charset, plain) in &plain_variants {
+                                if !seen_plain.insert(plain) {
+                                    continue;
+                                }
+                                for (label, hash) in calculate_checksums(plain, &self.checksum_params) {
+                                    if hash == target {
+                                        let found = InferenceMatch::new(&label, &hash, "", SaltSource::None, *charset, Overlap::Exact, target.len());
+                                        self.inference_results.push(InferenceResult::Match(found));
+                                    }
+                                }
+                            }
+                        }
+
+                        let mut match_count = self.inference_results.iter().filter(|r| r.as_match().is_some()).count();
+                        // 不加盐的配方与盐值无关，每组明文字节只匹配一次，避免逐个盐值重复命中挤掉加盐结果
+                        let mut unsalted_seen = std::collections::HashSet::new();
+                        'search: for (salt_index, (salt, salt_source)) in salts_to_try.into_iter().enumerate() {
+                            let mut seen_pairs = std::collections::HashSet::new();
+                            for (i, (charset, plain)) in plain_variants.iter().enumerate() {
+                                // 盐值无法用该字符集表示时跳过此组合；字节与已尝试的字符集相同时跳过，避免重复匹配
+                                let Ok(salt_bytes) = charset.encode_text(&salt) else { continue };
+                                if !seen_pairs.insert((plain, salt_bytes.clone())) {
+                                    continue;
+                                }
+                                // UTF-16 类算法与字符集无关，只在第一个字符集下计算
+                                let text = (i == 0).then_some(self.inference_plaintext.as_str());
+                                let mut candidates = calculate_complex_hashes(plain, text, &salt_bytes, &self.xof_params);
+                                // 较慢的格式只对用户填写的盐值计算，不参与盐值爆破
+                                if salt_index == 0 {
+                                    candidates.extend(calculate_slow_hashes(plain, &salt_bytes));
+                                }
+                                let try_unsalted = unsalted_seen.insert(plain);
                             
-                            for (label, hash) in candidates {
-                                let hash_lower = hash.to_lowercase();
-                                let is_match = if self.inference_fuzzy {
+                                for (label, hash) in candidates {
+                                    let salted = uses_salt(&label);
+                                    if !salted && !try_unsalted {
+                                        continue;
+                                    }
+                                    let (hash_cmp, target_cmp) = if crypto::is_text_row(&label) {
+                                        (hash.clone(), exact_target.as_str())
+                                    } else {
+                                        (hash.to_lowercase(), target.as_str())
+                                    };
                                     // 模糊匹配：目标包含哈希，或哈希包含目标
-                                    target.contains(&hash_lower) || hash_lower.contains(&target)
-                                } else {
-                                    // 精确匹配
-                                    hash_lower == target
-                                };
-
-                                if is_match {
-                                    let salt_info = if salt.is_empty() { "无盐".to_string() } else { format!("Salt='{}'", salt) };
-                                    self.inference_results.push(format!("✅ 匹配成功: [{}] ({}) -> {}", label, salt_info, hash));
-                                    found = true;
+                                    let Some(overlap) = Overlap::find(&hash_cmp, target_cmp, self.inference_fuzzy) else { continue };
+                                    let (salt, salt_source) = if salted { (salt.as_str(), salt_source) } else { ("", SaltSource::None) };
+                                    let found = InferenceMatch::new(&label, &hash, salt, salt_source, *charset, overlap, target_cmp.len());
+                                    self.inference_results.push(InferenceResult::Match(found));
                                     match_count += 1;
-                                    
-                                    // 限制显示数量，防止爆破出太多结果卡死
-                                    if match_count >= 50 {
-                                        self.inference_results.push("... 结果过多，已截断 ...".to_string());
-                                        break;
+
+                                    // 限制数量，防止爆破出太多结果卡死
+                                    if match_count >= MATCH_LIMIT {
+                                        self.inference_results.push(InferenceResult::Warning(format!("匹配超过 {} 个，已停止搜索", MATCH_LIMIT)));
+                                        break 'search;
                                     }
                                 }
                             }
-                            if match_count >= 50 { break; }
                         }
+                        let found = match_count > 0;
 
                         if !found {
-                            self.inference_results.push("⚠️ 未找到匹配的算法模式".to_string());
+                            self.inference_results.push(InferenceResult::Warning("未找到匹配的算法模式".to_string()));
                             if !self.inference_brute_salt {
-                                self.inference_results.push("尝试勾选 '爆破常见盐值' 进行更深入的搜索。".to_string());
+                                self.inference_results.push(InferenceResult::Info("尝试勾选 '爆破常见盐值' 进行更深入的搜索。".to_string()));
+                            }
+                            if !self.inference_charsets && !self.inference_plaintext.is_ascii() {
+                                self.inference_results.push(InferenceResult::Info("明文含非 ASCII 字符，可尝试勾选 '尝试多种字符集'。".to_string()));
                             }
                         } else {
-                            self.inference_results.insert(0, format!("🔍 分析完成，共尝试 {} 个盐值，发现 {} 个匹配项。", total_salts, match_count));
+                            let summary = format!("🔍 分析完成，共尝试 {} 个盐值，发现 {} 个匹配项。", total_salts, match_count);
+                            self.inference_results.push(InferenceResult::Info(summary));
                         }
                     }
                 }
@@ -739,15 +1527,62 @@ impl HashApp {
             ui.add_space(10.0);
             if !self.inference_results.is_empty() {
                 ui.group(|ui| {
-                    ui.heading("分析结果:");
+                    let has_matches = self.inference_results.iter().any(|r| r.as_match().is_some());
+                    ui.horizontal(|ui| {
+                        ui.heading("分析结果:");
+                        if has_matches {
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                Self::export_menu(ui, &mut self.export_settings, || {
+                                    self.inference_results
+                                        .iter()
+                                        .filter_map(InferenceResult::as_match)
+                                        .map(|m| m.to_export(&self.inference_plaintext))
+                                        .collect()
+                                });
+                            });
+                        }
+                    });
                     ui.separator();
                     for res in &self.inference_results {
-                        if res.starts_with("✅") {
-                            ui.label(egui::RichText::new(res).color(egui::Color32::GREEN).strong().size(16.0));
-                        } else if res.starts_with("❌") {
-                            ui.label(egui::RichText::new(res).color(egui::Color32::RED));
-                        } else {
-                            ui.label(res);
+                        match res {
+                            InferenceResult::Match(_) => {}
+                            InferenceResult::Known(entry) => {
+                                let salt_info = entry.salt.as_ref().map(|s| format!(", Salt='{}'", s)).unwrap_or_default();
+                                let text = format!("📚 potfile 已知: 明文='{}'{} (来自 {})", entry.plain_text(), salt_info, entry.source);
+                                ui.label(egui::RichText::new(text).color(egui::Color32::GREEN).strong());
+                            }
+                            InferenceResult::Info(msg) => {
+                                ui.label(msg);
+                            }
+                            InferenceResult::Warning(msg) => {
+                                ui.label(egui::RichText::new(format!("⚠️ {}", msg)).color(egui::Color32::YELLOW));
+                            }
+                            InferenceResult::Error(msg) => {
+                                ui.label(egui::RichText::new(format!("❌ {}", msg)).color(egui::Color32::RED));
+                            }
+                        }
+                    }
+                    if has_matches {
+                        ui.add_space(5.0);
+                        ui.horizontal(|ui| {
+                            ui.label("🔍 筛选:");
+                            ui.add(egui::TextEdit::singleline(&mut self.inference_filter).hint_text("配方或盐值...").desired_width(150.0));
+                            ui.checkbox(&mut self.inference_exact_only, "只看精确匹配");
+                            ui.checkbox(&mut self.inference_grouped, "按配方分组");
+                            if !self.inference_grouped {
+                                egui::ComboBox::from_id_salt("inference_sort")
+                                    .selected_text(self.inference_sort.name())
+                                    .show_ui(ui, |ui| {
+                                        for sort in InferenceSort::all() {
+                                            ui.selectable_value(&mut self.inference_sort, *sort, sort.name());
+                                        }
+                                    });
+                            }
+                        });
+                        ui.separator();
+                        if let Some((blocks, salt, charset)) = self.inference_match_table(ui) {
+                            let now = ui.input(|i| i.time);
+                            self.open_in_builder(blocks, salt, charset, now);
                         }
                     }
                 });
@@ -755,33 +1590,954 @@ impl HashApp {
         });
     }
 
-    fn calculate_blocks(&self, blocks: &[CustomBlock]) -> String {
-        let mut s = String::new();
-        for block in blocks {
-            match block {
-                CustomBlock::Password => s.push_str(&self.input_text),
-                CustomBlock::Salt => s.push_str(&self.salt_text),
-                CustomBlock::Literal(l) => s.push_str(l),
-                CustomBlock::Hash(algo, inner_blocks) => {
-                    let inner_content = self.calculate_blocks(inner_blocks);
-                    s.push_str(&calculate_hash(*algo, &inner_content, "", SaltMode::None, None));
+    /// 将推算出的配方载入加密计算页的积木区，并填入对应的明文、盐值与编码
+    fn open_in_builder(&mut self, blocks: Vec<CustomBlock>, salt: String, charset: InputEncoding, now: f64) {
+        if self.custom_blocks != blocks {
+            let before = std::mem::replace(&mut self.custom_blocks, blocks);
+            self.block_history.record(before, &self.custom_blocks, now);
+        }
+        self.input_text = self.inference_plaintext.clone();
+        self.salt_text = salt;
+        // 推算时盐值与明文使用同一字符集编码
+        self.input_format = InputFormat { encoding: charset, escapes: false };
+        self.salt_format = InputFormat { encoding: charset, escapes: false };
+        self.active_tool = ActiveTool::Encryption;
+        self.calculate();
+    }
+
+    /// 推算匹配表中的一行：得分、盐值、来源、编码、吻合程度与 (高亮匹配片段的) 哈希。
+    /// 点击 "在积木区打开" 时返回 true
+    fn inference_match_row(ui: &mut egui::Ui, m: &InferenceMatch, show_recipe: bool, show_charset: bool, buildable: bool) -> bool {
+        if show_recipe {
+            ui.monospace(&m.recipe);
+        }
+        ui.label(m.score.to_string());
+        ui.monospace(if m.salt.is_empty() { "-" } else { &m.salt });
+        ui.label(m.salt_source.name());
+        if show_charset {
+            ui.label(m.charset.name());
+        }
+        match &m.overlap {
+            Overlap::Exact => {
+                ui.label(egui::RichText::new("✅ 精确").color(egui::Color32::GREEN));
+            }
+            Overlap::HashContainsTarget(_) => {
+                ui.label(egui::RichText::new(format!("◐ {}%", m.quality)).color(egui::Color32::YELLOW))
+                    .on_hover_text("计算结果中包含目标 (目标可能被截断)");
+            }
+            Overlap::TargetContainsHash(_) => {
+                ui.label(egui::RichText::new(format!("◐ {}%", m.quality)).color(egui::Color32::YELLOW))
+                    .on_hover_text("目标中包含计算结果 (目标可能带前后缀)");
+            }
+        }
+        // 计算结果中包含目标时高亮被匹配的片段
+        if let Overlap::HashContainsTarget(span) = &m.overlap {
+            let mut job = egui::text::LayoutJob::default();
+            let font = egui::FontId::monospace(12.0);
+            let plain = egui::TextFormat { font_id: font.clone(), color: ui.visuals().text_color(), ..Default::default() };
+            let lower = m.hash.to_lowercase();
+            if lower.is_char_boundary(span.start) && lower.is_char_boundary(span.end) && lower.len() == m.hash.len() {
+                job.append(&m.hash[..span.start], 0.0, plain.clone());
+                job.append(
+                    &m.hash[span.clone()],
+                    0.0,
+                    egui::TextFormat { background: egui::Color32::from_rgb(100, 80, 30), ..plain.clone() },
+                );
+                job.append(&m.hash[span.end..], 0.0, plain);
+                ui.label(job);
+            } else {
+                ui.monospace(&m.hash);
+            }
+        } else {
+            ui.monospace(&m.hash);
+        }
+        let mut open = false;
+        ui.horizontal(|ui| {
+            if ui.small_button("📋").on_hover_text("复制公式").clicked() {
+                ui.output_mut(|o| o.copied_text = m.recipe.clone());
+            }
+            open = ui
+                .add_enabled(buildable, egui::Button::new("🧩").small())
+                .on_hover_text("在积木区打开")
+                .on_disabled_hover_text("该配方依赖 UTF-16、截断或特定格式，无法用积木表示")
+                .clicked();
+        });
+        ui.end_row();
+        open
+    }
+
+    /// 推算匹配：可按配方分组 (组与组内均按可信度得分排序) 或按所选方式平铺排序。
+    /// 某行点击 "在积木区打开" 时返回该配方的积木、盐值与编码
+    fn inference_match_table(&self, ui: &mut egui::Ui) -> Option<(Vec<CustomBlock>, String, InputEncoding)> {
+        let show_charset = self.inference_charsets;
+        let filter = self.inference_filter.to_lowercase();
+        let recipe_blocks = |recipe: &str| blocks::from_recipe(recipe, &self.xof_params, &self.checksum_params);
+        let visible = self
+            .inference_results
+            .iter()
+            .filter_map(InferenceResult::as_match)
+            .filter(|m| !self.inference_exact_only || m.is_exact())
+            .filter(|m| filter.is_empty() || m.recipe.to_lowercase().contains(&filter) || m.salt.to_lowercase().contains(&filter));
+        let header = |ui: &mut egui::Ui, show_recipe: bool| {
+            if show_recipe {
+                ui.strong("配方");
+            }
+            ui.strong("得分");
+            ui.strong("盐值");
+            ui.strong("盐值来源");
+            if show_charset {
+                ui.strong("编码");
+            }
+            ui.strong("吻合");
+            ui.strong("哈希");
+            ui.end_row();
+        };
+        let mut opened: Option<&InferenceMatch> = None;
+        if self.inference_grouped {
+            for (i, (recipe, items)) in ranked_groups(visible).into_iter().enumerate() {
+                let best = items[0];
+                let buildable = recipe_blocks(recipe).is_some();
+                let title = egui::RichText::new(format!("{}  —  {} 个匹配，最高 {} 分", recipe, items.len(), best.score));
+                let title = if best.is_exact() { title.color(egui::Color32::GREEN).strong() } else { title.color(egui::Color32::YELLOW) };
+                egui::CollapsingHeader::new(title)
+                    .id_salt(("inference_group", recipe))
+                    .default_open(i < 3)
+                    .show(ui, |ui| {
+                        egui::Grid::new(("inference_rows", recipe)).striped(true).spacing([12.0, 4.0]).show(ui, |ui| {
+                            header(ui, false);
+                            for m in items {
+                                if Self::inference_match_row(ui, m, false, show_charset, buildable) {
+                                    opened = Some(m);
+                                }
+                            }
+                        });
+                    });
+            }
+        } else {
+            let mut items: Vec<&InferenceMatch> = visible.collect();
+            self.inference_sort.sort(&mut items);
+            egui::Grid::new("inference_rows_flat").striped(true).spacing([12.0, 4.0]).show(ui, |ui| {
+                header(ui, true);
+                for m in items {
+                    if Self::inference_match_row(ui, m, true, show_charset, recipe_blocks(&m.recipe).is_some()) {
+                        opened = Some(m);
+                    }
                 }
+            });
+        }
+        let m = opened?;
+        let charset = blocks::recipe_input_encoding(&m.recipe).unwrap_or(m.charset);
+        Some((recipe_blocks(&m.recipe)?, m.salt.clone(), charset))
+    }
+
+    fn ui_verify(&mut self, ui: &mut egui::Ui) {
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.set_min_width(ui.available_width());
+            ui.heading("口令校验工具");
+            ui.separator();
+            ui.label("粘贴抓包或导出的完整哈希串，自动识别格式，并用候选口令重新计算以确认是否正确。");
+            ui.small("支持: NetNTLMv1 / NetNTLMv1-ESS / NetNTLMv2 (user::domain:...)、pwdump (user:rid:LM:NT:::)");
+            ui.small("Django (pbkdf2_sha256$...)、PHPass $P$ / $H$ (WordPress / phpBB3)、Drupal 7 $S$、Joomla / vBulletin / Discuz! (hash:salt)");
+            ui.small("LDAP {MD5} / {SHA} / {SSHA} / {SSHA256} / {SSHA512} / {CRYPT}、htpasswd ($apr1$、{SHA}、bcrypt，可带 user: 前缀)");
+            ui.add_space(10.0);
+
+            let mut changed = false;
+            ui.group(|ui| {
+                ui.label("哈希串:");
+                let stored = ui.add(
+                    egui::TextEdit::multiline(&mut self.verify_stored)
+                        .hint_text("例如: admin::CORP:1122334455667788:<NTProofStr>:<blob>")
+                        .desired_width(f32::INFINITY)
+                        .desired_rows(3),
+                );
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    ui.label("候选口令:");
+                    let password = ui.add(egui::TextEdit::singleline(&mut self.verify_password).desired_width(f32::INFINITY));
+                    changed = stored.changed() || password.changed();
+                });
+            });
+
+            // PBKDF2 / PHPass 等迭代格式计算较慢，只在输入变化时在后台重新计算；旧任务的结果直接丢弃
+            if changed {
+                self.verify_result = None;
+                self.verify_running = if self.verify_stored.trim().is_empty() {
+                    None
+                } else {
+                    let (stored, password) = (self.verify_stored.clone(), self.verify_password.clone());
+                    Some(std::thread::spawn(move || formats::verify(&stored, &password)))
+                };
             }
+            if self.verify_running.is_some() {
+                ui.add_space(10.0);
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label("正在计算...");
+                });
+            }
+            let Some(result) = &self.verify_result else {
+                return;
+            };
+            ui.add_space(10.0);
+
+            ui.group(|ui| {
+                let verification = match result {
+                    Ok(verification) => verification,
+                    Err(e) => {
+                        ui.label(egui::RichText::new(format!("❌ {}", e)).color(egui::Color32::RED));
+                        return;
+                    }
+                };
+                ui.label(egui::RichText::new(format!("识别为: {}", verification.stored.name())).strong());
+                ui.add_space(5.0);
+                egui::Grid::new("verify_fields").num_columns(2).spacing([10.0, 4.0]).show(ui, |ui| {
+                    for (label, value) in verification.stored.fields() {
+                        ui.label(label);
+                        ui.monospace(value);
+                        ui.end_row();
+                    }
+                    ui.label("计算值");
+                    ui.monospace(&verification.computed);
+                    ui.end_row();
+                });
+                ui.add_space(5.0);
+                if verification.matched() {
+                    ui.label(egui::RichText::new("✅ 口令正确").color(egui::Color32::GREEN).strong().size(16.0));
+                } else {
+                    ui.label(egui::RichText::new("❌ 口令不匹配").color(egui::Color32::RED));
+                }
+            });
+        });
+    }
+
+    fn start_batch(&mut self) {
+        let recipe = if self.batch_use_formula {
+            BatchRecipe::Formula(self.custom_blocks.clone())
+        } else {
+            BatchRecipe::Algorithm(self.batch_algo, self.batch_params.clone())
+        };
+        if let Err(e) = recipe.validate() {
+            self.batch_status = Some(Err(format!("参数错误: {}", e)));
+            return;
+        }
+        let source = if self.batch_use_file {
+            if self.batch_file_path.trim().is_empty() {
+                self.batch_status = Some(Err("请先选择输入文件".to_string()));
+                return;
+            }
+            BatchSource::File(self.batch_file_path.trim().into())
+        } else {
+            BatchSource::Text(self.batch_text.clone())
+        };
+        let output_path = self.batch_output_path.trim();
+        self.batch_recipe = recipe.describe();
+        self.batch_mode = match &recipe {
+            BatchRecipe::Algorithm(algo, params) if params.is_standard(*algo) => algo.hashcat_mode(),
+            _ => None,
+        };
+        self.batch_encoding = self.batch_format.describe();
+        self.batch_uses_salt = matches!(recipe, BatchRecipe::Formula(_));
+        self.batch_rows.clear();
+        self.batch_overflow = 0;
+        self.batch_progress = (0, 0);
+        self.batch_status = None;
+        self.batch_job = Some(BatchJob::start(BatchSpec {
+            source,
+            salt_separator: self.batch_salt_lines.then(|| self.batch_separator.clone()),
+            format: self.batch_format,
+            recipe,
+            display: self.display_format,
+            output: (!output_path.is_empty()).then(|| output_path.into()),
+        }));
+    }
+
+    fn poll_batch(&mut self, ctx: &egui::Context) {
+        let Some(job) = &self.batch_job else {
+            return;
+        };
+        let mut finished = false;
+        for event in job.poll() {
+            match event {
+                BatchEvent::Rows(rows) => {
+                    let room = BATCH_ROW_LIMIT.saturating_sub(self.batch_rows.len());
+                    self.batch_overflow += rows.len().saturating_sub(room);
+                    self.batch_rows.extend(rows.into_iter().take(room));
+                }
+                BatchEvent::Progress { done, total } => self.batch_progress = (done, total),
+                BatchEvent::Finished { lines, elapsed, cancelled } => {
+                    let verb = if cancelled { "已取消" } else { "完成" };
+                    self.batch_status = Some(Ok(format!("{}：共 {} 行，用时 {:.2} 秒", verb, lines, elapsed.as_secs_f64())));
+                    finished = true;
+                }
+                BatchEvent::Failed(e) => {
+                    self.batch_status = Some(Err(e));
+                    finished = true;
+                }
+            }
+        }
+        if finished {
+            self.batch_job = None;
+        } else {
+            ctx.request_repaint_after(std::time::Duration::from_millis(50));
+        }
+    }
+
+    fn ui_batch(&mut self, ui: &mut egui::Ui) {
+        ui.heading("批量计算");
+        ui.separator();
+        ui.label("对多行口令 (或字典文件) 逐行计算，可选每行附带盐值；大文件在后台流式处理。");
+        ui.add_space(5.0);
+
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.batch_use_file, false, "直接输入");
+                ui.radio_value(&mut self.batch_use_file, true, "读取文件");
+                ui.separator();
+                ui.label("编码:");
+                let mut unused = false;
+                Self::input_format_editor(ui, "batch_format", &mut self.batch_format, &mut unused);
+            });
+            if self.batch_use_file {
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.batch_file_path)
+                        .hint_text("输入路径或拖入文件 (每行一个口令)")
+                        .desired_width(f32::INFINITY),
+                );
+                let dropped_path = ui.ctx().input(|i| {
+                    i.raw.dropped_files.first().and_then(|file| file.path.as_ref()).map(|path| path.display().to_string())
+                });
+                if let Some(path) = dropped_path {
+                    self.batch_file_path = path;
+                }
+            } else {
+                egui::ScrollArea::vertical().id_salt("batch_text").max_height(120.0).show(ui, |ui| {
+                    ui.add(
+                        egui::TextEdit::multiline(&mut self.batch_text)
+                            .hint_text("每行一个口令，例如:\n123456\nadmin:salt")
+                            .desired_width(f32::INFINITY)
+                            .desired_rows(5),
+                    );
+                });
+            }
+
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.batch_salt_lines, "每行带盐值")
+                    .on_hover_text("按最后一个分隔符拆分为 口令 / 盐值，没有分隔符的行盐值为空");
+                if self.batch_salt_lines {
+                    ui.label("分隔符:");
+                    ui.add(egui::TextEdit::singleline(&mut self.batch_separator).desired_width(30.0));
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.batch_use_formula, false, "单一算法");
+                ui.radio_value(&mut self.batch_use_formula, true, "自定义公式");
+                if self.batch_use_formula {
+                    ui.monospace(blocks::formula(&self.custom_blocks)).on_hover_text("在 '加密计算' 页的积木区编辑");
+                } else {
+                    egui::ComboBox::from_id_salt("batch_algo")
+                        .selected_text(self.batch_algo.name())
+                        .show_ui(ui, |ui| {
+                            for algo in HashAlgorithm::all() {
+                                // 换算法时参数回到该算法的标准值，避免沿用上一个算法的输出长度或密钥
+                                if ui.selectable_value(&mut self.batch_algo, *algo, algo.name()).changed() {
+                                    self.batch_params = HashParams::for_algo(*algo);
+                                }
+                            }
+                        });
+                    let mut unused = false;
+                    Self::params_editor(ui, self.batch_algo, &mut self.batch_params, &mut unused);
+                    if self.batch_salt_lines {
+                        ui.small("(单一算法忽略盐值，加盐请用自定义公式)");
+                    }
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("输出文件 (CSV，可选):");
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.batch_output_path)
+                        .hint_text("留空则只在下方显示")
+                        .desired_width(f32::INFINITY),
+                )
+                .on_hover_text("边计算边写入，不受显示行数限制");
+            });
+            ui.horizontal(|ui| {
+                ui.label("显示格式:");
+                egui::ComboBox::from_id_salt("batch_display_format")
+                    .selected_text(self.display_format.name())
+                    .show_ui(ui, |ui| {
+                        for format in DisplayFormat::all() {
+                            ui.selectable_value(&mut self.display_format, *format, format.name());
+                        }
+                    })
+                    .response
+                    .on_hover_text("原始字节结果的显示格式 (与加密计算页共用)，在开始计算时生效");
+            });
+
+            ui.horizontal(|ui| {
+                if let Some(job) = &self.batch_job {
+                    if ui.button("⏹ 停止").clicked() {
+                        job.cancel();
+                    }
+                    let (done, total) = self.batch_progress;
+                    let fraction = if total > 0 { done as f32 / total as f32 } else { 0.0 };
+                    ui.add(egui::ProgressBar::new(fraction).show_percentage().animate(true));
+                } else if ui.button("🚀 开始计算").clicked() {
+                    self.start_batch();
+                }
+            });
+            match &self.batch_status {
+                Some(Ok(msg)) => {
+                    ui.label(egui::RichText::new(format!("✅ {}", msg)).color(egui::Color32::GREEN));
+                }
+                Some(Err(e)) => {
+                    ui.label(egui::RichText::new(format!("❌ {}", e)).color(egui::Color32::RED));
+                }
+                None => {}
+            }
+        });
+
+        if self.batch_rows.is_empty() {
+            return;
+        }
+        ui.add_space(5.0);
+        ui.horizontal(|ui| {
+            ui.label(format!("算法: {}", self.batch_recipe));
+            ui.label(format!("共 {} 行", self.batch_rows.len() + self.batch_overflow));
+            if self.batch_overflow > 0 {
+                ui.label(
+                    egui::RichText::new(format!("(仅显示前 {} 行，完整结果请写入输出文件)", BATCH_ROW_LIMIT))
+                        .color(egui::Color32::YELLOW),
+                );
+            }
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                Self::export_menu(ui, &mut self.export_settings, || {
+                    self.batch_rows
+                        .iter()
+                        .filter_map(|row| {
+                            let output = row.result.as_ref().ok()?;
+                            Some(ExportRecord {
+                                algorithm: self.batch_recipe.clone(),
+                                hashcat_mode: self.batch_mode,
+                                hash: output.hash.clone(),
+                                salt: row.salt.clone().filter(|_| self.batch_uses_salt),
+                                plain: Some(output.plain.clone()),
+                                encoding: self.batch_encoding.clone(),
+                            })
+                        })
+                        .collect()
+                });
+                if ui.button("📋 复制表格").on_hover_text("以制表符分隔复制全部显示的行").clicked() {
+                    let mut text = String::new();
+                    for row in &self.batch_rows {
+                        let hash = match &row.result {
+                            Ok(output) => output.display.clone(),
+                            Err(e) => format!("错误: {}", e),
+                        };
+                        text.push_str(&format!("{}\t{}\t{}\n", row.input, row.salt.as_deref().unwrap_or(""), hash));
+                    }
+                    ui.output_mut(|o| o.copied_text = text);
+                }
+            });
+        });
+        ui.separator();
+
+        let row_height = ui.text_style_height(&egui::TextStyle::Monospace) + ui.spacing().item_spacing.y;
+        let with_salt = self.batch_rows.iter().any(|row| row.salt.is_some());
+        egui::ScrollArea::both().id_salt("batch_rows").auto_shrink([false, false]).show_rows(
+            ui,
+            row_height,
+            self.batch_rows.len(),
+            |ui, range| {
+                egui::Grid::new("batch_grid").striped(true).num_columns(4).show(ui, |ui| {
+                    for row in &self.batch_rows[range] {
+                        ui.label(row.line.to_string());
+                        ui.monospace(&row.input);
+                        if with_salt {
+                            ui.monospace(row.salt.as_deref().unwrap_or(""));
+                        }
+                        match &row.result {
+                            Ok(output) => {
+                                ui.monospace(&output.display);
+                            }
+                            Err(e) => {
+                                ui.label(egui::RichText::new(e).color(egui::Color32::RED));
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+            },
+        );
+    }
+
+    fn load_potfile(&mut self) {
+        let path = std::path::PathBuf::from(self.pot_path.trim());
+        self.pot_status = None;
+        self.pot_loading = Some(std::thread::spawn(move || PotIndex::from_file(&path)));
+    }
+
+    fn poll_verify(&mut self, ctx: &egui::Context) {
+        let Some(handle) = &self.verify_running else {
+            return;
+        };
+        if !handle.is_finished() {
+            ctx.request_repaint_after(std::time::Duration::from_millis(50));
+            return;
+        }
+        let Some(handle) = self.verify_running.take() else {
+            return;
+        };
+        self.verify_result = Some(handle.join().unwrap_or_else(|_| Err("校验线程异常退出".to_string())));
+    }
+
+    fn poll_potfile(&mut self, ctx: &egui::Context) {
+        let Some(handle) = &self.pot_loading else {
+            return;
+        };
+        if !handle.is_finished() {
+            ctx.request_repaint_after(std::time::Duration::from_millis(100));
+            return;
+        }
+        let Some(handle) = self.pot_loading.take() else {
+            return;
+        };
+        self.pot_status = Some(match handle.join() {
+            Ok(Ok(index)) => {
+                let (name, count) = index.files.last().cloned().unwrap_or_default();
+                self.pot_index.merge(index);
+                Ok(format!("已加载 {}: {} 条", name, count))
+            }
+            Ok(Err(e)) => Err(e),
+            Err(_) => Err("加载线程异常退出".to_string()),
+        });
+    }
+
+    fn open_lookup_table(&mut self) {
+        let path = std::path::PathBuf::from(self.table_path.trim());
+        self.lookup_tables.retain(|t| t.path != path);
+        match LookupTable::open(&path) {
+            Ok(table) => {
+                self.table_status = Some(Ok(format!("已打开 {}: {} 条", path.display(), table.entries)));
+                self.lookup_tables.push(table);
+            }
+            Err(e) => self.table_status = Some(Err(e)),
+        }
+        self.table_hits_key = None;
+    }
+
+    fn start_table_job(&mut self) {
+        let path = std::path::PathBuf::from(self.table_path.trim());
+        // 归并完成后会替换表文件，先关闭已打开的同名表
+        self.lookup_tables.retain(|t| t.path != path);
+        self.table_hits_key = None;
+        self.table_status = None;
+        self.table_progress = ("", 0, 0);
+        self.table_job = Some(TableJob::start(path, self.table_recipe, self.table_wordlist.trim().into()));
+    }
+
+    fn poll_table_job(&mut self, ctx: &egui::Context) {
+        let Some(job) = &self.table_job else {
+            return;
+        };
+        let mut finished = false;
+        for event in job.poll() {
+            match event {
+                TableEvent::Progress { phase, done, total } => self.table_progress = (phase, done, total),
+                TableEvent::Finished { added, entries, skipped } => {
+                    let mut status = format!("新增 {} 条，表中共 {} 条", added, entries);
+                    if skipped > 0 {
+                        status.push_str(&format!("，跳过 {} 条无法计算的口令", skipped));
+                    }
+                    self.table_status = Some(Ok(status));
+                    finished = true;
+                }
+                TableEvent::Failed(e) => {
+                    self.table_status = Some(Err(e));
+                    finished = true;
+                }
+            }
+        }
+        if finished {
+            self.table_job = None;
+            if matches!(self.table_status, Some(Ok(_))) {
+                let status = self.table_status.take();
+                self.open_lookup_table();
+                if matches!(self.table_status, Some(Ok(_))) {
+                    self.table_status = status;
+                }
+            }
+        } else {
+            ctx.request_repaint_after(std::time::Duration::from_millis(100));
+        }
+    }
+
+    fn refresh_table_hits(&mut self) {
+        self.table_hits.clear();
+        for target in self.lookup_targets.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            let hash = target.split(':').next().unwrap_or(target).trim_start_matches('*');
+            let mut hits = Vec::new();
+            for candidate in decode_target(hash) {
+                for table in &mut self.lookup_tables {
+                    match table.lookup(&candidate) {
+                        Ok(Some(plain)) => hits.push((format!("查找表 {}", table.recipe.name()), plain)),
+                        Ok(None) => {}
+                        Err(e) => hits.push((format!("查找表 {}", table.recipe.name()), format!("❌ {}", e))),
+                    }
+                }
+            }
+            if !hits.is_empty() {
+                self.table_hits.insert(target.to_string(), hits);
+            }
+        }
+        self.table_hits_key = Some(self.lookup_targets.clone());
+    }
+
+    fn ui_lookup(&mut self, ui: &mut egui::Ui) {
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.set_min_width(ui.available_width());
+            ui.heading("已知哈希查询");
+            ui.separator();
+            ui.label("加载团队维护的 hashcat potfile (hash:plain 或 hash:salt:plain)，目标哈希若已破解可直接得到明文，无需再做碰撞分析。");
+            ui.add_space(10.0);
+
+            ui.group(|ui| {
+                ui.horizontal(|ui| {
+                    ui.label("potfile:");
+                    ui.add(egui::TextEdit::singleline(&mut self.pot_path).hint_text("输入路径或拖入文件").desired_width(300.0));
+                    let loading = self.pot_loading.is_some();
+                    if ui.add_enabled(!loading && !self.pot_path.trim().is_empty(), egui::Button::new("📂 加载")).clicked() {
+                        self.load_potfile();
+                    }
+                    if loading {
+                        ui.spinner();
+                    }
+                    if ui.add_enabled(!self.pot_index.is_empty(), egui::Button::new("🗑 清空索引")).clicked() {
+                        self.pot_index = PotIndex::default();
+                        self.pot_status = None;
+                    }
+                });
+                // 拖入的文件按扩展名分配：potfile / 查找表 / 其余视为建表用的字典
+                let dropped_path = ui.ctx().input(|i| i.raw.dropped_files.first().and_then(|file| file.path.clone()));
+                if let Some(path) = dropped_path {
+                    let display = path.display().to_string();
+                    match path.extension().and_then(|e| e.to_str()) {
+                        Some("pot" | "potfile") => self.pot_path = display,
+                        Some("tbl") => self.table_path = display,
+                        _ => self.table_wordlist = display,
+                    }
+                }
+                match &self.pot_status {
+                    Some(Ok(msg)) => {
+                        ui.label(egui::RichText::new(format!("✅ {}", msg)).color(egui::Color32::GREEN));
+                    }
+                    Some(Err(e)) => {
+                        ui.label(egui::RichText::new(format!("❌ {}", e)).color(egui::Color32::RED));
+                    }
+                    None => {}
+                }
+                for (name, count) in &self.pot_index.files {
+                    ui.small(format!("• {}: {} 条", name, count));
+                }
+                if !self.pot_index.files.is_empty() {
+                    ui.small(format!("共 {} 条记录", self.pot_index.len()));
+                }
+            });
+
+            ui.add_space(10.0);
+            ui.group(|ui| {
+                ui.label("本地查找表 (md5 / sha1 / ntlm / md5(md5) 等无盐快速算法):");
+                ui.horizontal(|ui| {
+                    ui.label("表文件:");
+                    ui.add(egui::TextEdit::singleline(&mut self.table_path).hint_text("例如 D:/tables/md5.tbl").desired_width(300.0));
+                    if ui.add_enabled(!self.table_path.trim().is_empty(), egui::Button::new("📂 打开")).clicked() {
+                        self.open_lookup_table();
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("字典:");
+                    ui.add(egui::TextEdit::singleline(&mut self.table_wordlist).hint_text("每行一个口令").desired_width(300.0));
+                    egui::ComboBox::from_id_salt("table_recipe")
+                        .selected_text(self.table_recipe.name())
+                        .show_ui(ui, |ui| {
+                            for recipe in TableRecipe::all() {
+                                ui.selectable_value(&mut self.table_recipe, *recipe, recipe.name());
+                            }
+                        });
+                });
+                ui.horizontal(|ui| {
+                    if let Some(job) = &self.table_job {
+                        if ui.button("⏹ 停止").clicked() {
+                            job.cancel();
+                        }
+                        let (phase, done, total) = self.table_progress;
+                        let fraction = if total > 0 { done as f32 / total as f32 } else { 0.0 };
+                        ui.add(egui::ProgressBar::new(fraction).text(phase).animate(true));
+                    } else {
+                        let ready = !self.table_path.trim().is_empty() && !self.table_wordlist.trim().is_empty();
+                        if ui
+                            .add_enabled(ready, egui::Button::new("🔨 构建 / 追加"))
+                            .on_hover_text("表文件已存在时把字典中的新口令归并进去 (配方须一致)")
+                            .clicked()
+                        {
+                            self.start_table_job();
+                        }
+                    }
+                });
+                match &self.table_status {
+                    Some(Ok(msg)) => {
+                        ui.label(egui::RichText::new(format!("✅ {}", msg)).color(egui::Color32::GREEN));
+                    }
+                    Some(Err(e)) => {
+                        ui.label(egui::RichText::new(format!("❌ {}", e)).color(egui::Color32::RED));
+                    }
+                    None => {}
+                }
+                let mut close = None;
+                for (i, table) in self.lookup_tables.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.small(format!(
+                            "• {} [{}]: {} 条, {:.1} MB",
+                            table.path.display(),
+                            table.recipe.name(),
+                            table.entries,
+                            table.size_on_disk() as f64 / (1024.0 * 1024.0)
+                        ));
+                        if ui.small_button("✖").on_hover_text("关闭").clicked() {
+                            close = Some(i);
+                        }
+                    });
+                }
+                if let Some(i) = close {
+                    self.lookup_tables.remove(i);
+                    self.table_hits_key = None;
+                }
+            });
+
+            ui.add_space(10.0);
+            ui.group(|ui| {
+                ui.label("目标哈希 (每行一个，可带 :salt):");
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.lookup_targets)
+                        .hint_text("e10adc3949ba59abbe56e057f20f883e")
+                        .desired_width(f32::INFINITY)
+                        .desired_rows(4),
+                );
+            });
+
+            // 查找表需要读盘，只在目标或已打开的表变化时重新查询
+            if self.table_hits_key.as_deref() != Some(self.lookup_targets.as_str()) {
+                self.refresh_table_hits();
+            }
+            let targets: Vec<&str> = self.lookup_targets.lines().map(str::trim).filter(|l| !l.is_empty()).collect();
+            if targets.is_empty() {
+                return;
+            }
+            ui.add_space(10.0);
+            let mut hits = 0;
+            ui.group(|ui| {
+                egui::Grid::new("lookup_results").striped(true).num_columns(4).spacing([10.0, 4.0]).show(ui, |ui| {
+                    ui.strong("目标");
+                    ui.strong("明文");
+                    ui.strong("盐值");
+                    ui.strong("来源");
+                    ui.end_row();
+                    for target in &targets {
+                        let entries = self.pot_index.lookup(target);
+                        let table_hits = self.table_hits.get(*target).map(Vec::as_slice).unwrap_or_default();
+                        for (source, plain) in table_hits {
+                            ui.monospace(*target);
+                            ui.label(egui::RichText::new(plain).color(egui::Color32::GREEN).strong());
+                            ui.label("");
+                            ui.small(source);
+                            ui.end_row();
+                        }
+                        if !table_hits.is_empty() && entries.is_empty() {
+                            hits += 1;
+                            continue;
+                        }
+                        if entries.is_empty() {
+                            ui.monospace(*target);
+                            ui.label(egui::RichText::new("未找到").color(egui::Color32::GRAY));
+                            ui.end_row();
+                            continue;
+                        }
+                        if table_hits.is_empty() {
+                            hits += 1;
+                        }
+                        for entry in entries {
+                            ui.monospace(*target);
+                            ui.label(egui::RichText::new(entry.plain_text()).color(egui::Color32::GREEN).strong());
+                            ui.monospace(entry.salt.as_deref().unwrap_or(""));
+                            ui.small(&entry.source);
+                            ui.end_row();
+                        }
+                    }
+                });
+                ui.separator();
+                ui.label(format!("已知 {} / {} 个目标", hits, targets.len()));
+            });
+        });
+    }
+
+    fn ui_ethereum(&mut self, ui: &mut egui::Ui) {
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.set_min_width(ui.available_width());
+            ui.heading("以太坊工具");
+            ui.separator();
+            ui.label("基于 Keccak-256 (非标准 SHA3-256) 的常用计算，结果随输入实时更新。");
+            ui.add_space(10.0);
+
+            ui.group(|ui| {
+                ui.label("函数 / 事件签名:");
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.eth_signature)
+                        .hint_text("例如: Transfer(address,address,uint256)")
+                        .desired_width(f32::INFINITY),
+                );
+                let normalized = ethereum::normalize_signature(&self.eth_signature);
+                if normalized != self.eth_signature {
+                    ui.small(format!("已去除空白: {}", normalized));
+                }
+                ui.add_space(5.0);
+                egui::Grid::new("eth_signature_grid").num_columns(3).spacing([10.0, 6.0]).show(ui, |ui| {
+                    let rows = [
+                        ("函数选择器", ethereum::function_selector(&self.eth_signature)),
+                        ("事件 Topic", ethereum::event_topic(&self.eth_signature)),
+                    ];
+                    for (label, value) in rows {
+                        ui.label(label);
+                        ui.monospace(&value);
+                        if ui.button("📋").on_hover_text("复制").clicked() {
+                            ui.output_mut(|o| o.copied_text = value.clone());
+                        }
+                        ui.end_row();
+                    }
+                });
+            });
+
+            ui.add_space(10.0);
+
+            ui.group(|ui| {
+                ui.label("EIP-55 校验和地址:");
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.eth_address)
+                        .hint_text("0x 开头的 40 位十六进制地址")
+                        .desired_width(f32::INFINITY),
+                );
+                if self.eth_address.trim().is_empty() {
+                    return;
+                }
+                match ethereum::checksum_address(&self.eth_address) {
+                    Ok((checksummed, status)) => {
+                        ui.horizontal(|ui| {
+                            ui.label("校验和地址:");
+                            ui.monospace(&checksummed);
+                            if ui.button("📋").on_hover_text("复制").clicked() {
+                                ui.output_mut(|o| o.copied_text = checksummed.clone());
+                            }
+                        });
+                        match status {
+                            ethereum::ChecksumStatus::NoChecksum => {
+                                ui.label("输入为全小写/全大写，不含校验信息");
+                            }
+                            ethereum::ChecksumStatus::Valid => {
+                                ui.label(egui::RichText::new("✅ 输入地址校验和正确").color(egui::Color32::GREEN));
+                            }
+                            ethereum::ChecksumStatus::Invalid => {
+                                ui.label(egui::RichText::new("❌ 输入地址大小写与校验和不符，可能有输入错误").color(egui::Color32::RED));
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        ui.label(egui::RichText::new(format!("❌ {}", e)).color(egui::Color32::RED));
+                    }
+                }
+            });
+        });
+    }
+
+    /// 收取后台积木计算的结果
+    fn poll_evaluator(&mut self, ctx: &egui::Context) {
+        if let Some(outcome) = self.evaluator.poll() {
+            // 最外层若以 raw 输出，结果可能不是合法 UTF-8，此时按全局显示格式显示
+            match outcome.output.map(String::from_utf8) {
+                Ok(Ok(text)) => {
+                    self.output_text = text;
+                    self.output_is_binary = false;
+                }
+                Ok(Err(e)) => {
+                    self.output_text = self.display_format.format(e.as_bytes());
+                    self.output_is_binary = true;
+                }
+                Err(e) => {
+                    self.output_text = format!("参数错误: {}", e);
+                    self.output_is_binary = false;
+                }
+            }
+            self.custom_trace = outcome.trace;
+            if let Some(check) = outcome.check {
+                self.codegen_check = check;
+            }
+        }
+        if self.evaluator.is_busy() {
+            ctx.request_repaint_after(std::time::Duration::from_millis(50));
         }
-        s
     }
 
     fn calculate(&mut self) {
+        // 0. 按输入编码转换，任一字段转换失败时清空结果
+        self.input_bytes = self.input_format.bytes(&self.input_text);
+        self.salt_bytes = self.salt_format.bytes(&self.salt_text);
+        let (pass, salt) = match (&self.input_bytes, &self.salt_bytes) {
+            (Ok(pass), Ok(salt)) => (pass.clone(), salt.clone()),
+            _ => {
+                self.bulk_results.clear();
+                self.checksum_results.clear();
+                self.custom_trace.clear();
+                self.output_text.clear();
+                self.output_is_binary = false;
+                self.evaluator.cancel();
+                return;
+            }
+        };
+
         // 1. Bulk Calculation
-        self.bulk_results = calculate_complex_hashes(&self.input_text, &self.salt_text);
+        let text = self.input_format.text(&self.input_text);
+        self.bulk_results = calculate_complex_hashes(&pass, text.as_deref(), &salt, &self.xof_params);
+        self.bulk_results.extend(calculate_slow_hashes(&pass, &salt));
+        self.checksum_results = calculate_checksums(&pass, &self.checksum_params);
 
         // 2. Custom Block Calculation
-        self.output_text = self.calculate_blocks(&self.custom_blocks);
+        if let Err(e) = blocks::validate(&self.custom_blocks) {
+            self.output_text = format!("参数错误: {}", e);
+            self.output_is_binary = false;
+            self.custom_trace.clear();
+            self.evaluator.cancel();
+        } else {
+            // 自检使用固定样例，只在积木变化时重新进行
+            let check = self.checked_blocks.as_ref() != Some(&self.custom_blocks);
+            if check {
+                self.checked_blocks = Some(self.custom_blocks.clone());
+            }
+            self.evaluator.submit(self.custom_blocks.clone(), pass, salt, check);
+        }
+
+        // 3. 导出代码
+        let program = codegen::Program::lower(&self.custom_blocks);
+        self.codegen_output = codegen::generate(self.codegen_language, &program, &self.input_text, &self.salt_text);
     }
 }
 
 impl eframe::App for HashApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.poll_evaluator(ctx);
+        self.poll_verify(ctx);
+        // 切换到其它页面时批量任务仍在后台运行，结果照常收取
+        self.poll_batch(ctx);
+        self.poll_potfile(ctx);
+        self.poll_table_job(ctx);
         egui::SidePanel::left("side_panel")
             .resizable(true)
             .default_width(170.0)
@@ -791,19 +2547,51 @@ impl eframe::App for HashApp {
                 
                 ui.selectable_value(&mut self.active_tool, ActiveTool::Encryption, "🔐 加密计算");
                 ui.selectable_value(&mut self.active_tool, ActiveTool::Inference, "🔍 算法推算");
+                ui.selectable_value(&mut self.active_tool, ActiveTool::Verify, "🔑 口令校验");
+                ui.selectable_value(&mut self.active_tool, ActiveTool::Batch, "📑 批量计算");
+                ui.selectable_value(&mut self.active_tool, ActiveTool::Lookup, "📚 已知哈希");
+                ui.selectable_value(&mut self.active_tool, ActiveTool::Ethereum, "⛓ 以太坊工具");
                 
                 ui.with_layout(egui::Layout::bottom_up(egui::Align::Min), |ui| {
                     ui.add_space(5.0);
                     ui.horizontal(|ui| {
                         ui.spacing_mut().item_spacing.x = 5.0;
                         ui.label("当前版本:");
-                        ui.label(egui::RichText::new("v0.7.2").color(egui::Color32::from_rgb(100, 200, 100)).strong());
+                        ui.label(egui::RichText::new("v0.8.0").color(egui::Color32::from_rgb(100, 200, 100)).strong());
                     });
 
                     ui.separator();
                     ui.collapsing("📢 更新日志", |ui| {
                         egui::ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
                             ui.vertical(|ui| {
+                                ui.label(egui::RichText::new("v0.8.0").strong());
+                                ui.small("• 哈希积木支持输出编码 (raw / hex / HEX / Base64)");
+                                ui.small("• 新增循环积木，支持 md5^N 等多轮哈希");
+                                ui.small("• 积木区支持撤销/重做 (Ctrl+Z / Ctrl+Y) 与历史记录");
+                                ui.small("• 积木支持跨层级拖放，可从工具栏直接拖入任意位置");
+                                ui.small("• 自定义公式可导出为 PHP/Python/JS/Java/Go/SQL/Rust 代码");
+                                ui.small("• 新增计算过程视图，可逐层查看每个积木的中间输入与输出");
+                                ui.small("• 新增 SHAKE128/256、cSHAKE、KangarooTwelve、BLAKE3 XOF，可指定输出长度");
+                                ui.small("• 新增 Keccak-256/512 与以太坊工具 (函数选择器、事件 Topic、EIP-55 地址)");
+                                ui.small("• BLAKE2/BLAKE3 积木支持输出长度、密钥、salt、personalization 与 derive_key");
+                                ui.small("• 新增 SHA-512/224、SHA-512/256、Streebog、GOST R 34.11-94、Tiger、MD2、RIPEMD-128/256/320");
+                                ui.small("• 新增非加密校验和：CRC32/CRC32C/CRC64、Adler-32、xxHash、MurmurHash3、FNV、SipHash");
+                                ui.small("• 新增口令校验工具：支持 LM、NetNTLMv1/v1-ESS、NetNTLMv2");
+                                ui.small("• 口令校验支持 Django、PHPass (WordPress/phpBB3)、Drupal 7、Joomla、vBulletin、Discuz!");
+                                ui.small("• 新增 LDAP {SSHA} 等与 htpasswd $apr1$ 格式的生成与校验，自动提取盐值");
+                                ui.small("• 新增结果显示格式 (hex/HEX/Base64/Base64URL/Base32/冒号分隔/C 数组)，支持逐行设置与一键复制");
+                                ui.small("• 对比支持 hex/Base64/0x 前缀等多种目标格式，并区分显示截断等部分匹配");
+                                ui.small("• 口令与盐值可选输入编码 (UTF-8/UTF-16/GBK/GB18030/Latin-1/hex/Base64)，支持 \\xNN 等转义");
+                                ui.small("• 算法推算可自动尝试 GBK/UTF-16LE/Big5/Latin-1 等字符集，并标注匹配所用编码");
+                                ui.small("• 新增批量计算：多行口令或字典文件逐行计算，支持每行盐值、自定义公式与 CSV 流式输出");
+                                ui.small("• 批量结果、批量计算表格与推算匹配可导出为 CSV/JSON/hashcat 哈希列表/potfile，含模式号与编码");
+                                ui.small("• 新增已知哈希查询：加载 hashcat potfile 建立索引，推算前先查询目标是否已破解");
+                                ui.small("• 新增本地查找表：由字典为 md5/sha1/ntlm/md5(md5) 构建磁盘有序表，二分查询并支持追加字典");
+                                ui.small("• 推算结果按可信度评分排序并按配方分组，模糊匹配按吻合比例降权");
+                                ui.small("• 推算结果支持筛选、切换排序、只看精确匹配、复制公式，并高亮截断匹配的片段");
+                                ui.small("• 推算匹配可一键在积木区打开，自动生成等价积木并填入明文、盐值与编码");
+                                ui.add_space(2.0);
+
                                 ui.label(egui::RichText::new("v0.7.2").strong());
                                 ui.small("• 批量计算新增 SM3/RIPEMD/Whirlpool/SHA3/BLAKE 等算法");
                                 ui.add_space(2.0);
@@ -894,6 +2682,10 @@ impl eframe::App for HashApp {
             match self.active_tool {
                 ActiveTool::Encryption => self.ui_encryption(ui),
                 ActiveTool::Inference => self.ui_inference(ui),
+                ActiveTool::Verify => self.ui_verify(ui),
+                ActiveTool::Batch => self.ui_batch(ui),
+                ActiveTool::Lookup => self.ui_lookup(ui),
+                ActiveTool::Ethereum => self.ui_ethereum(ui),
             }
         });
     }
@@ -0,0 +1,86 @@
+use des::cipher::{generic_array::GenericArray, BlockEncrypt, KeyInit};
+use des::Des;
+use hmac::{Hmac, Mac};
+use md4::{Digest, Md4};
+use md5::Md5;
+
+/// LM 哈希用 DES 加密的固定明文
+const LM_MAGIC: &[u8; 8] = b"KGS!@#$%";
+
+/// 7 字节扩展为 DES 的 8 字节密钥 (每字节低位为奇偶校验位，DES 忽略)
+fn des_key(key7: &[u8]) -> [u8; 8] {
+    let mut key = [0u8; 8];
+    key[0] = key7[0] >> 1;
+    for i in 1..7 {
+        key[i] = ((key7[i - 1] << (7 - i)) | (key7[i] >> (i + 1))) & 0x7f;
+    }
+    key[7] = key7[6] & 0x7f;
+    key.map(|b| b << 1)
+}
+
+fn des_encrypt(key7: &[u8], block: &[u8; 8]) -> [u8; 8] {
+    let cipher = Des::new(&des_key(key7).into());
+    let mut out = GenericArray::clone_from_slice(block);
+    cipher.encrypt_block(&mut out);
+    out.into()
+}
+
+fn utf16le(text: &str) -> Vec<u8> {
+    text.encode_utf16().flat_map(|u| u.to_le_bytes()).collect()
+}
+
+/// LM 哈希：口令转大写后补零/截断到 14 字节，两半分别作为 DES 密钥加密 "KGS!@#$%"。
+/// 超过 14 字节的口令 Windows 不会保存 LM，这里按截断计算。
+pub fn lm_hash(password: &str) -> [u8; 16] {
+    let mut key = [0u8; 14];
+    for (dst, src) in key.iter_mut().zip(password.to_ascii_uppercase().bytes()) {
+        *dst = src;
+    }
+    let mut out = [0u8; 16];
+    out[..8].copy_from_slice(&des_encrypt(&key[..7], LM_MAGIC));
+    out[8..].copy_from_slice(&des_encrypt(&key[7..], LM_MAGIC));
+    out
+}
+
+/// NT 哈希：MD4(UTF-16LE(口令))
+pub fn nt_hash(password: &str) -> [u8; 16] {
+    Md4::digest(utf16le(password)).into()
+}
+
+/// NetNTLMv1 响应：NT 哈希补零到 21 字节，分三段作为 DES 密钥加密 8 字节挑战值
+pub fn netntlmv1_response(nt_hash: &[u8; 16], challenge: &[u8; 8]) -> [u8; 24] {
+    let mut key = [0u8; 21];
+    key[..16].copy_from_slice(nt_hash);
+    let mut out = [0u8; 24];
+    for (i, chunk) in key.chunks(7).enumerate() {
+        out[i * 8..(i + 1) * 8].copy_from_slice(&des_encrypt(chunk, challenge));
+    }
+    out
+}
+
+/// NTLMv1-ESS (扩展会话安全) 实际使用的挑战值：MD5(服务端挑战 + 客户端挑战) 的前 8 字节
+pub fn ess_challenge(server: &[u8; 8], client: &[u8; 8]) -> [u8; 8] {
+    let hash = Md5::new().chain_update(server).chain_update(client).finalize();
+    let mut out = [0u8; 8];
+    out.copy_from_slice(&hash[..8]);
+    out
+}
+
+fn hmac_md5(key: &[u8], parts: &[&[u8]]) -> [u8; 16] {
+    let mut mac = <Hmac<Md5> as Mac>::new_from_slice(key).expect("HMAC 接受任意长度密钥");
+    for part in parts {
+        mac.update(part);
+    }
+    mac.finalize().into_bytes().into()
+}
+
+/// NTLMv2 哈希：HMAC-MD5(NT 哈希, UTF-16LE(大写用户名 + 域名))
+pub fn ntlmv2_hash(nt_hash: &[u8; 16], user: &str, domain: &str) -> [u8; 16] {
+    let identity = format!("{}{}", user.to_uppercase(), domain);
+    hmac_md5(nt_hash, &[&utf16le(&identity)])
+}
+
+/// NetNTLMv2 的 NTProofStr：HMAC-MD5(NTLMv2 哈希, 服务端挑战 + blob)
+pub fn ntproofstr(v2_hash: &[u8; 16], server_challenge: &[u8; 8], blob: &[u8]) -> [u8; 16] {
+    hmac_md5(v2_hash, &[server_challenge, blob])
+}
@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// potfile 中一条已破解记录 (按查询目标拆分出的盐值与明文)
+#[derive(Clone)]
+pub struct PotEntry {
+    /// 哈希之后、明文之前的字段 (如 `hash:salt` 中的盐值)
+    pub salt: Option<String>,
+    /// 明文字节，`$HEX[...]` 已解码，可能不是 UTF-8 (如 GBK 口令)
+    pub plain: Vec<u8>,
+    /// 来源文件名
+    pub source: String,
+}
+
+impl PotEntry {
+    /// 显示用的明文：合法 UTF-8 原样显示，否则写成 `$HEX[...]`
+    pub fn plain_text(&self) -> String {
+        match std::str::from_utf8(&self.plain) {
+            Ok(text) => text.to_string(),
+            Err(_) => format!("$HEX[{}]", hex::encode(&self.plain)),
+        }
+    }
+}
+
+/// 文件中的一行：哈希之后的全部内容。
+/// 无盐格式的明文可以含 `:` (hashcat 原样写出)，所以是否有盐值要到查询时按目标决定
+struct PotRecord {
+    rest: Vec<u8>,
+    source: String,
+}
+
+/// 已破解哈希索引，键为首个字段 (哈希本身) 的小写形式
+#[derive(Default)]
+pub struct PotIndex {
+    entries: HashMap<String, Vec<PotRecord>>,
+    /// 已加载的文件及各自的有效行数
+    pub files: Vec<(String, usize)>,
+}
+
+/// 解码 hashcat 的 `$HEX[...]` 明文，非法时原样返回
+fn decode_plain(plain: &[u8]) -> Vec<u8> {
+    plain
+        .strip_prefix(b"$HEX[")
+        .and_then(|rest| rest.strip_suffix(b"]"))
+        .and_then(|digits| hex::decode(digits).ok())
+        .unwrap_or_else(|| plain.to_vec())
+}
+
+/// 查询用的哈希键：去掉 mysql5 的 `*` 前缀并转小写，`hash:salt` 只取哈希部分
+fn hash_key(hash: &str) -> String {
+    let hash = hash.trim();
+    let hash = hash.split(':').next().unwrap_or(hash);
+    hash.trim_start_matches('*').to_lowercase()
+}
+
+impl PotIndex {
+    /// 读取 `hash[:salt]:plain` 格式的 potfile，按行首的哈希建立索引
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| format!("无法打开 {}: {}", path.display(), e))?;
+        let source = path.file_name().map_or_else(|| path.display().to_string(), |n| n.to_string_lossy().into_owned());
+        Self::from_reader(BufReader::new(file), &source)
+    }
+
+    fn from_reader(mut reader: impl BufRead, source: &str) -> Result<Self, String> {
+        let mut index = PotIndex::default();
+        let mut count = 0;
+        let mut buf = Vec::new();
+        loop {
+            buf.clear();
+            let read = reader.read_until(b'\n', &mut buf).map_err(|e| format!("读取 {} 失败: {}", source, e))?;
+            if read == 0 {
+                break;
+            }
+            while buf.last().is_some_and(|b| *b == b'\n' || *b == b'\r') {
+                buf.pop();
+            }
+            let Some(colon) = buf.iter().position(|b| *b == b':') else { continue };
+            let hash = String::from_utf8_lossy(&buf[..colon]);
+            if hash.is_empty() {
+                continue;
+            }
+            let record = PotRecord { rest: buf[colon + 1..].to_vec(), source: source.to_string() };
+            index.entries.entry(hash_key(&hash)).or_default().push(record);
+            count += 1;
+        }
+        index.files.push((source.to_string(), count));
+        Ok(index)
+    }
+
+    /// 合并另一个索引 (如新加载的文件)
+    pub fn merge(&mut self, other: PotIndex) {
+        for (key, mut entries) in other.entries {
+            self.entries.entry(key).or_default().append(&mut entries);
+        }
+        self.files.extend(other.files);
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.iter().map(|(_, count)| count).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 查询目标哈希。目标带盐值 (`hash:salt`) 时只返回以该盐值开头的记录，其后为明文；
+    /// 不带盐值时按无盐格式处理，第一个 `:` 之后的内容整体是明文 (可以含 `:`)
+    pub fn lookup(&self, target: &str) -> Vec<PotEntry> {
+        let target = target.trim();
+        let salt = target.split_once(':').map(|(_, salt)| salt);
+        let Some(records) = self.entries.get(&hash_key(target)) else { return Vec::new() };
+        records
+            .iter()
+            .filter_map(|record| {
+                let plain = match salt {
+                    Some(salt) => record.rest.strip_prefix(salt.as_bytes())?.strip_prefix(b":")?,
+                    None => &record.rest,
+                };
+                Some(PotEntry {
+                    salt: salt.map(str::to_string),
+                    plain: decode_plain(plain),
+                    source: record.source.clone(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index(text: &str) -> PotIndex {
+        PotIndex::from_reader(text.as_bytes(), "test.pot").unwrap()
+    }
+
+    #[test]
+    fn unsalted_plain_with_colon() {
+        let index = index("5f4dcc3b5aa765d61d8327deb882cf99:pa:ss\n");
+        let entries = index.lookup("5F4DCC3B5AA765D61D8327DEB882CF99");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].plain, b"pa:ss");
+        assert_eq!(entries[0].salt, None);
+    }
+
+    #[test]
+    fn salted_line() {
+        let index = index("0123456789abcdef0123456789abcdef:salt:plain\r\n");
+        let entries = index.lookup("0123456789abcdef0123456789abcdef:salt");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].plain, b"plain");
+        assert_eq!(entries[0].salt.as_deref(), Some("salt"));
+        assert!(index.lookup("0123456789abcdef0123456789abcdef:other").is_empty());
+        assert_eq!(index.len(), 1);
+    }
+
+    #[test]
+    fn hex_plain_keeps_bytes() {
+        let index = index("*6BB4837EB74329105EE4568DDA7DC67ED2CA2AD9:$HEX[c3dcc2eb]\nno colon line\n");
+        let entries = index.lookup("6bb4837eb74329105ee4568dda7dc67ed2ca2ad9");
+        assert_eq!(entries[0].plain, [0xc3, 0xdc, 0xc2, 0xeb]);
+        assert_eq!(entries[0].plain_text(), "$HEX[c3dcc2eb]");
+        assert_eq!(index.len(), 1);
+    }
+}